@@ -0,0 +1,44 @@
+//! An example to send a bang via FUDI over UDP to a pure data patch
+//! and wait for the reply on another port (request/response).
+//!
+//! The patch "ping_pong.pd" answers every message with a bang:
+//! ```text
+//! [loadbang]
+//! |
+//! [listen 39943(
+//! |
+//! [netreceive -u]
+//! |
+//! [t b b]
+//! |     \
+//! |      [connect 127.0.0.1 18539(
+//! |      /
+//! [send bang(
+//! |
+//! [netsend -u]
+//! ```
+extern crate fudi_rs; // add crate to talk to pure data
+
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    println!("press CTRL + C to stop"); // print helpful hint
+
+    // listen on 127.0.0.1:18539 for replies and send to 127.0.0.1:39943
+    let netreceive = fudi_rs::NetReceiveUdp::new("127.0.0.1:18539");
+    let netsend = fudi_rs::NetSendUdp::new("127.0.0.1:39943");
+
+    // forever do ...
+    loop {
+        println!("sending bang");
+        netsend
+            .send(&fudi_rs::PdMessage::Bang)
+            .expect("sending message failed");
+        match netreceive.receive() {
+            Ok(reply) => println!("received {:?}", reply),
+            Err(e) => panic!("{}", e),
+        }
+        thread::sleep(Duration::from_secs(1)); // sleep for 1 second
+    }
+}
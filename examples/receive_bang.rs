@@ -1,5 +1,5 @@
-///! An example to receive bangs via FUDI over UDP
-///! from a pure data patch.
+//! An example to receive bangs via FUDI over UDP
+//! from a pure data patch.
 extern crate fudi_rs; // add crate to talk to pure data
 
 fn main() {
@@ -0,0 +1,38 @@
+//! An example to send a list with mixed atoms via FUDI over UDP
+//! to a pure data patch every second.
+//!
+//! The patch "send_list.pd" splits the list into its atoms:
+//! ```text
+//! [listen 39944(
+//! |
+//! [netreceive -u]
+//! |
+//! [unpack f s f]
+//! |      |     \
+//! [f]    [sym]  [f]
+//! ```
+extern crate fudi_rs; // add crate to talk to pure data
+
+use fudi_rs::{Atom, PdMessage};
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    println!("press CTRL + C to stop"); // print helpful hint
+
+    // create new netsend with 127.0.0.1:39944 as destination for messages
+    let netsend = fudi_rs::NetSendUdp::new("127.0.0.1:39944");
+
+    // forever do ...
+    for step in 0.. {
+        // create a list message with a number, a symbol, and another number
+        let msg = PdMessage::List(vec![
+            Atom::Int(step),
+            Atom::symbol_escaped("hello pd"), // the space is escaped -> still one atom
+            Atom::Float(step as f32 / 10.0),
+        ]);
+        println!("sending {}", msg.to_text().trim_end());
+        netsend.send(&msg).expect("sending message failed"); // actually send the list message
+        thread::sleep(Duration::from_secs(1)); // sleep for 1 second
+    }
+}
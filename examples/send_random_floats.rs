@@ -1,11 +1,11 @@
+//! An example to send random floats via FUDI over UDP
+//! to a pure data patch every second.
+extern crate fudi_rs; // add crate to talk to pure data
+
 use rand::Rng;
 use std::thread;
 use std::time::Duration;
 
-///! An example to send random floats via FUDI over UDP
-///! to a pure data patch every second.
-extern crate fudi_rs; // add crate to talk to pure data
-
 fn main() {
     println!("press CTRL + C to stop"); // print helpful hint
 
@@ -0,0 +1,9 @@
+//! Feed arbitrary bytes to the parser, which must never panic.
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = fudi_rs::get_message(data);
+    let _ = fudi_rs::get_message_strict(data);
+    let _ = fudi_rs::get_messages(data);
+});
@@ -0,0 +1,111 @@
+//! Pure Data messages borrowing their atoms from the received payload.
+
+use crate::{parser, GenericMessage, ParseError, ParserOptions, PdMessage, DEFAULT_MAX_ATOMS};
+
+/// A message whose atoms borrow from the payload it was parsed from,
+/// so inspecting a message does not allocate a string per atom.
+///
+/// The atoms are kept as they are on the wire (i.e. escaped and not
+/// interpreted as numbers).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BorrowedMessage<'a> {
+    selector: &'a str,
+    atoms: Vec<&'a str>,
+}
+
+impl<'a> BorrowedMessage<'a> {
+    /// Get the first atom of the message (e.g. `float` or `3` in `3 4;`).
+    pub fn selector(&self) -> &'a str {
+        self.selector
+    }
+
+    /// Get the atoms following the selector.
+    pub fn atoms(&self) -> &[&'a str] {
+        &self.atoms
+    }
+
+    /// Copy the message into an owned `PdMessage`, which may outlive the payload.
+    ///
+    /// The atoms are interpreted like `get_message` does. If they do not match the
+    /// selector (e.g. `symbol 1;`), the result is a generic message keeping all atoms.
+    pub fn to_owned(&self) -> PdMessage {
+        let mut atoms = vec![];
+        for word in std::iter::once(&self.selector).chain(self.atoms.iter()) {
+            // the words are valid UTF-8 and not empty -> always an atom
+            if let Ok(Some(atom)) = parser::token_to_atom(word.as_bytes()) {
+                atoms.push(atom);
+            }
+        }
+        match parser::atoms_to_message(atoms.clone(), &ParserOptions::default()) {
+            Ok(msg) => msg,
+            Err(_) => PdMessage::Generic(GenericMessage::new(self.selector, atoms.split_off(1))),
+        }
+    }
+}
+
+/// Retrieve a Pure Data message from byte payload without copying its atoms.
+///
+/// # Examples
+/// ```rust
+/// let msg = fudi_rs::get_message_borrowed(b"note 60 127;\n").unwrap();
+/// assert_eq!("note", msg.selector());
+/// assert_eq!(&["60", "127"], msg.atoms());
+/// ```
+pub fn get_message_borrowed<'a>(payload: &'a [u8]) -> Result<BorrowedMessage<'a>, ParseError> {
+    let mut atoms = vec![];
+    for token in parser::tokenize(payload, DEFAULT_MAX_ATOMS)? {
+        match std::str::from_utf8(token) {
+            Ok(atom) => atoms.push(atom),
+            Err(_) => return Err(ParseError::InvalidUtf8),
+        }
+    }
+    if atoms.is_empty() {
+        return Err(ParseError::Malformed);
+    }
+    let selector = atoms.remove(0);
+    Ok(BorrowedMessage { selector, atoms })
+}
+
+#[cfg(test)]
+mod test_borrowedmessage {
+    use super::*;
+
+    #[test]
+    fn atoms_point_into_payload() {
+        let payload = b"note 60\\ 1 127;\n".to_vec();
+        let msg = get_message_borrowed(&payload).expect("parsing failed");
+        assert_eq!("note", msg.selector());
+        assert_eq!(&["60\\ 1", "127"], msg.atoms());
+
+        let range = payload.as_ptr_range();
+        assert!(range.contains(&msg.selector().as_ptr()));
+        for atom in msg.atoms() {
+            assert!(range.contains(&atom.as_ptr()));
+        }
+    }
+
+    #[test]
+    fn convert_to_owned_message() {
+        let payload = b"float 3;\n".to_vec();
+        let msg = get_message_borrowed(&payload).expect("parsing failed");
+        let owned = msg.to_owned();
+        drop(payload);
+        assert_eq!(Some(3.0), owned.as_float());
+
+        let msg = get_message_borrowed(b"symbol 1;\n").expect("parsing failed");
+        assert_eq!("symbol 1;\n", msg.to_owned().to_text());
+    }
+
+    #[test]
+    fn reject_malformed_payload() {
+        assert_eq!(Err(ParseError::Malformed), get_message_borrowed(b";\n"));
+        assert_eq!(
+            Err(ParseError::MissingTerminator),
+            get_message_borrowed(b"bang\n")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidUtf8),
+            get_message_borrowed(b"symbol \xff;\n")
+        );
+    }
+}
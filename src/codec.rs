@@ -0,0 +1,69 @@
+//! Encode and decode FUDI messages without any IO (i.e. sans-IO).
+//!
+//! The socket types build on these functions. Use them directly to
+//! talk to Pure Data via any other transport (e.g. a serial line or
+//! a WebSocket).
+//!
+//! # Examples
+//! ```rust
+//! use fudi_rs::{codec, PdMessage};
+//! let bytes = codec::encode(&PdMessage::Float(3.0));
+//! assert_eq!(b"float 3;\n".to_vec(), bytes);
+//! assert_eq!(Ok(PdMessage::Float(3.0)), codec::decode(&bytes));
+//! ```
+
+use crate::{parser, ParseError, PdMessage};
+
+/// Serialize a message into its FUDI bytes (e.g. `float 3;\n`).
+///
+/// # Arguments
+/// * `msg` - message to serialize
+pub fn encode(msg: &PdMessage) -> Vec<u8> {
+    msg.to_bytes()
+}
+
+/// Parse the first message of a FUDI payload (see `get_message`).
+///
+/// # Arguments
+/// * `payload` - FUDI payload of the message
+pub fn decode(payload: &[u8]) -> Result<PdMessage, ParseError> {
+    parser::get_message(payload)
+}
+
+#[cfg(test)]
+mod test_codec {
+    use super::*;
+    use crate::{Atom, GenericMessage, Symbol};
+
+    #[test]
+    fn encode_messages() {
+        assert_eq!(b"bang;\n".to_vec(), encode(&PdMessage::Bang));
+        assert_eq!(
+            b"symbol a\\ b;\n".to_vec(),
+            encode(&PdMessage::Symbol(Symbol::escaped("a b")))
+        );
+    }
+
+    #[test]
+    fn decode_messages() {
+        assert_eq!(Ok(PdMessage::Bang), decode(b"bang;\n"));
+        assert_eq!(Err(ParseError::MissingTerminator), decode(b"bang"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let msgs = [
+            PdMessage::Bang,
+            PdMessage::Float(-1.5),
+            PdMessage::Symbol(Symbol::escaped("foo;bar")),
+            PdMessage::List(vec![Atom::Int(1), Atom::symbol_escaped("a")]),
+            PdMessage::Generic(GenericMessage::new(
+                "note",
+                vec![Atom::Int(1), Atom::symbol_escaped("a b")],
+            )),
+        ];
+        for msg in msgs.iter() {
+            assert_eq!(Ok(msg.clone()), decode(&encode(msg)));
+        }
+    }
+}
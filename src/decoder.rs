@@ -0,0 +1,141 @@
+//! Decode Pure Data messages from a stream of bytes arriving in arbitrary chunks.
+
+use crate::{parser, ParseError, PdMessage, DEFAULT_MAX_MESSAGE_BYTES};
+
+/// Accumulate bytes (e.g. read from a stream) and yield the complete messages.
+///
+/// Bytes after the last (unescaped) semicolon are kept until the rest
+/// of the message arrives. A message exceeding the size limit is reported
+/// as `ParseError::TooLong` and the buffered bytes are discarded.
+///
+/// # Examples
+/// ```rust
+/// let mut decoder = fudi_rs::Decoder::new();
+/// decoder.push(b"float 4");
+/// assert!(decoder.next_message().is_none());
+/// decoder.push(b"2;\n");
+/// let msg = decoder.next_message().unwrap().unwrap();
+/// assert_eq!(Some(42.0), msg.as_float());
+/// ```
+#[derive(Debug)]
+pub struct Decoder {
+    buffer: Vec<u8>,
+    max_message_bytes: usize,
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Decoder {
+            buffer: vec![],
+            max_message_bytes: DEFAULT_MAX_MESSAGE_BYTES,
+        }
+    }
+}
+
+impl Decoder {
+    /// Create a new instance with an empty buffer.
+    pub fn new() -> Decoder {
+        Decoder::default()
+    }
+
+    /// Limit the number of bytes buffered while waiting for the
+    /// terminating semicolon of a message (default: `DEFAULT_MAX_MESSAGE_BYTES`).
+    ///
+    /// # Arguments
+    /// * `max` - maximum size of a message (in bytes)
+    pub fn set_max_message_bytes(&mut self, max: usize) {
+        self.max_message_bytes = max;
+    }
+
+    /// Append bytes to the internal buffer.
+    ///
+    /// # Arguments
+    /// * `bytes` - bytes received from the source
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    // Get the buffered bytes not belonging to a complete message yet.
+    pub(crate) fn remainder(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Take the next complete message out of the buffer.
+    /// Returns `None` if no complete message has been buffered yet.
+    pub fn next_message(&mut self) -> Option<Result<PdMessage, ParseError>> {
+        loop {
+            let end = match parser::find_terminator(&self.buffer) {
+                Some(end) if end < self.max_message_bytes => end,
+                None if self.buffer.len() <= self.max_message_bytes => return None,
+                _ => {
+                    self.buffer.clear();
+                    return Some(Err(ParseError::TooLong));
+                }
+            };
+            let frame: Vec<u8> = self.buffer.drain(..=end).collect();
+            match parser::get_messages(&frame) {
+                Ok(mut messages) => {
+                    if let Some(msg) = messages.pop() {
+                        return Some(Ok(msg));
+                    }
+                }
+                Err(msg) => return Some(Err(msg)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_decoder {
+    use super::*;
+
+    #[test]
+    fn decode_fragmented_input() {
+        let mut decoder = Decoder::new();
+        decoder.push(b"ba");
+        assert!(decoder.next_message().is_none());
+        decoder.push(b"ng;\nfloat");
+        assert!(decoder.next_message().unwrap().unwrap().is_bang());
+        assert!(decoder.next_message().is_none());
+        decoder.push(b" 3;\n;\nsymbol a\\;b;\n");
+        assert_eq!(
+            Some(3.0),
+            decoder.next_message().unwrap().unwrap().as_float()
+        );
+        assert_eq!(
+            Some("a\\;b"),
+            decoder.next_message().unwrap().unwrap().as_symbol()
+        );
+        assert!(decoder.next_message().is_none());
+    }
+
+    #[test]
+    fn keep_partial_remainder() {
+        let mut decoder = Decoder::new();
+        decoder.push(b"bang;\nfoo\\;");
+        assert!(decoder.next_message().unwrap().unwrap().is_bang());
+        assert!(decoder.next_message().is_none());
+        decoder.push(b" bar;\n");
+        assert_eq!(
+            "foo\\; bar;\n",
+            decoder.next_message().unwrap().unwrap().to_text()
+        );
+    }
+
+    #[test]
+    fn reject_overlong_message() {
+        let mut decoder = Decoder::new();
+        decoder.set_max_message_bytes(8);
+        decoder.push(b"symbol ");
+        assert!(decoder.next_message().is_none());
+        decoder.push(b"foo");
+        assert_eq!(Some(Err(ParseError::TooLong)), decoder.next_message());
+        assert!(decoder.remainder().is_empty());
+
+        // the decoder recovers with the next message
+        decoder.push(b"bang;\nsymbol foobar;\n");
+        assert!(decoder.next_message().unwrap().unwrap().is_bang());
+        assert_eq!(Some(Err(ParseError::TooLong)), decoder.next_message());
+        assert!(decoder.next_message().is_none());
+    }
+}
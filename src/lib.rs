@@ -19,41 +19,265 @@
 //! * [Pure Data message implementation notes](https://puredata.info/dev/PdMessages)
 //! * [undocumented internal messages](https://puredata.info/docs/tutorials/TipsAndTricks#undocumented-pd-internal-messages)
 
-use std::io::{Error, ErrorKind, Result};
-use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
-use std::str::FromStr;
+use socket2::{Domain, Protocol, SockRef, Socket, Type};
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Result};
+use std::mem;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 #[macro_use]
 extern crate nom;
 
+mod borrowed;
+pub mod codec;
+mod decoder;
 mod parser;
+mod sequence;
+#[cfg(feature = "srv")]
+mod srv;
+mod tcp;
+#[cfg(test)]
+mod testutil;
+#[cfg(unix)]
+mod unix;
+#[cfg(feature = "ws")]
+pub mod ws;
+pub use borrowed::{get_message_borrowed, BorrowedMessage};
+pub use decoder::Decoder;
+pub use parser::{
+    escape_atom, get_message, get_message_strict, get_message_with, get_messages,
+    get_messages_with, is_valid_atom, messages_from_lines, parse_atoms, parse_atoms_with,
+    peek_selector, unescape_atom, validate, validate_with, ParseError, ParserLimits, ParserOptions,
+    DEFAULT_MAX_ATOMS, DEFAULT_MAX_MESSAGES, DEFAULT_MAX_MESSAGE_BYTES,
+};
+pub use sequence::{add_sequence_number, strip_sequence_number, SequenceEvent, SequenceTracker};
+#[cfg(feature = "srv")]
+pub use srv::{resolve_srv, SrvRecord, SrvResolver};
+pub use tcp::{NetReceiveTcp, NetSendTcp};
+#[cfg(unix)]
+pub use unix::{NetReceiveUnix, NetSendUnix};
 
-/// An implementation of the most generic Pure Data message type.
-#[derive(Debug)]
+/// Number of messages buffered between a receiving thread and its consumer.
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Maximum payload of a UDP datagram (65,535 − 8 byte UDP header − 20 byte IP header),
+/// i.e. the largest serialized message `NetSendUdp` can send.
+pub const MAX_UDP_PAYLOAD: usize = 65535 - 8 - 20;
+
+/// An implementation of the most generic Pure Data message type
+/// (i.e. a custom message), consisting of a selector and typed atoms.
+///
+/// # Examples
+/// ```rust
+/// use fudi_rs::{Atom, GenericMessage, PdMessage};
+/// let msg = GenericMessage::new("myobj", vec![Atom::Int(1), Atom::symbol_escaped("foo")]);
+/// assert_eq!("myobj 1 foo;\n", PdMessage::Generic(msg).to_text());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GenericMessage {
     selector: String,
-    atoms: Vec<String>,
+    atoms: Vec<Atom>,
+}
+
+impl GenericMessage {
+    /// Create a message from a selector and the atoms following it.
+    ///
+    /// *note*: The selector is used verbatim, it has to be a valid (escaped) atom.
+    ///
+    /// # Arguments
+    /// * `selector` - selector of the message
+    /// * `atoms` - atoms following the selector
+    pub fn new(selector: &str, atoms: Vec<Atom>) -> GenericMessage {
+        GenericMessage {
+            selector: selector.to_string(),
+            atoms,
+        }
+    }
+
+    /// Get the selector of the message.
+    pub fn selector(&self) -> &str {
+        &self.selector
+    }
+
+    /// Get the atoms following the selector.
+    pub fn atoms(&self) -> &[Atom] {
+        &self.atoms
+    }
+
+    /// Iterate over the atoms following the selector.
+    pub fn atoms_iter(&self) -> impl Iterator<Item = &Atom> {
+        self.atoms.iter()
+    }
+
+    /// Convert the message into the specific message type matching its selector
+    /// (e.g. `float 3` into `PdMessage::Float`), normalized like `get_message`
+    /// does with the default `ParserOptions` (i.e. `list` is a bang and `list 5`
+    /// a float message). A message whose selector is not built-in or whose atoms
+    /// do not fit the selector (e.g. `bang 1` or `symbol 1`) stays generic.
+    pub fn specialize(self) -> PdMessage {
+        match (self.selector.as_str(), self.atoms.as_slice()) {
+            ("bang", []) | ("list", []) => PdMessage::Bang,
+            ("float", [Atom::Float(f)]) | ("list", [Atom::Float(f)]) => PdMessage::Float(*f),
+            ("float", [Atom::Int(i)]) | ("list", [Atom::Int(i)]) => PdMessage::Float(*i as f32),
+            ("symbol", [Atom::Symbol(_)]) | ("list", [Atom::Symbol(_)]) => {
+                match self.atoms.into_iter().next() {
+                    Some(Atom::Symbol(word)) => PdMessage::Symbol(Symbol(word)),
+                    _ => unreachable!(),
+                }
+            }
+            ("list", _) => PdMessage::List(self.atoms),
+            _ => PdMessage::Generic(self),
+        }
+    }
 }
 
 /// An implementation of the (generic) atom data type.
-#[derive(Debug)]
-pub struct Atom {
-    number: Option<f32>,
-    word: Option<String>,
+///
+/// A number atom without fraction or exponent (e.g. `60` or `-3`) is an `Int`,
+/// so it keeps its integer nature, any other number (e.g. `60.5` or `1e3`) is a `Float`.
+///
+/// Floats are compared and hashed by their bit pattern, so atoms can be
+/// used in a `HashSet`. *note*: A NaN equals a NaN with the same bit pattern
+/// and `0.0` does not equal `-0.0` (unlike comparing the floats).
+#[derive(Debug, Clone)]
+pub enum Atom {
+    Float(f32),
+    Int(i64),
+    Symbol(String),
+}
+
+impl PartialEq for Atom {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Atom::Float(a), Atom::Float(b)) => a.to_bits() == b.to_bits(),
+            (Atom::Int(a), Atom::Int(b)) => a == b,
+            (Atom::Symbol(a), Atom::Symbol(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Atom {}
+
+impl Hash for Atom {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            Atom::Float(f) => f.to_bits().hash(state),
+            Atom::Int(i) => i.hash(state),
+            Atom::Symbol(word) => word.hash(state),
+        }
+    }
+}
+
+impl Atom {
+    /// Get the value of a number atom (i.e. `Float` or `Int`) as float.
+    pub fn as_float(&self) -> Option<f32> {
+        match self {
+            Atom::Float(f) => Some(*f),
+            Atom::Int(i) => Some(*i as f32),
+            Atom::Symbol(_) => None,
+        }
+    }
+
+    /// Create a symbol atom from any string by escaping whitespace,
+    /// semicolons, and backslashes (see `escape_atom`), so it is
+    /// serialized as a single atom.
+    ///
+    /// # Arguments
+    /// * `word` - the unescaped string
+    pub fn symbol_escaped(word: &str) -> Atom {
+        Atom::Symbol(escape_atom(word))
+    }
+}
+
+impl fmt::Display for Atom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Atom::Float(value) => write!(f, "{}", value),
+            Atom::Int(value) => write!(f, "{}", value),
+            Atom::Symbol(word) => f.write_str(word),
+        }
+    }
+}
+
+/// A symbol which is serialized as exactly one FUDI atom.
+///
+/// # Examples
+/// ```rust
+/// use fudi_rs::Symbol;
+///
+/// assert!(Symbol::new("foo bar").is_none());
+/// assert_eq!("foo\\ bar", Symbol::escaped("foo bar").as_str());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Symbol(String);
+
+impl Symbol {
+    /// Create a symbol from a string which is a single (escaped) atom.
+    /// Returns `None` if the string is not exactly one atom (see `is_valid_atom`).
+    ///
+    /// # Arguments
+    /// * `word` - the (escaped) atom
+    pub fn new(word: &str) -> Option<Symbol> {
+        if is_valid_atom(word) {
+            Some(Symbol(word.to_string()))
+        } else {
+            None
+        }
+    }
+
+    /// Create a symbol from any string by escaping whitespace,
+    /// semicolons, and backslashes (see `escape_atom`).
+    ///
+    /// # Arguments
+    /// * `word` - the unescaped string
+    pub fn escaped(word: &str) -> Symbol {
+        Symbol(escape_atom(word))
+    }
+
+    /// Get the (escaped) atom of the symbol.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
 }
 
 /// An incomplete implementation of Pure Data message types.
 ///
 /// # implemented
 /// * Float messages
-/// * Symbol messages (based on strings)
+/// * Symbol messages (always a single atom, see `Symbol`)
 /// * Bang messages
-/// * custom/generic message
+/// * List messages (including the implied list, e.g. `1 2 3;`)
+/// * custom/generic message (selector and typed atoms, see `GenericMessage`)
 ///
 /// # not implemented
-/// * list
 /// * pointer
 ///
+/// Dollar arguments of message boxes (e.g. `$1` in `set $1;`) are plain symbol
+/// atoms and passed through unchanged. The substitution is done by Pure Data, not fudi-rs.
+///
+/// Messages implement `Eq` and `Hash` (e.g. for deduplication in a `HashSet`),
+/// floats are compared by their bit pattern like in `Atom`.
+///
+/// Floats are serialized as Rust formats them, so NaN and infinity become
+/// `NaN` and `inf` (e.g. `float inf;`), which Pure Data (and `get_message`)
+/// reads as symbols, so they do not come back as floats.
+/// Use `PdMessage::try_float` or `NetSendUdp::send_checked` to reject them.
+///
 /// # Examples
 /// Create a message to send a (floating point) number.
 /// ```rust
@@ -63,45 +287,468 @@ pub struct Atom {
 /// # references
 /// * [FLOSS Manuals: Pure Data - messages](http://write.flossmanuals.net/pure-data/messages/)
 /// * [puredata.info: PdMessages](https://puredata.info/dev/PdMessages)
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum PdMessage {
     Float(f32),
-    Symbol(String),
+    Symbol(Symbol),
     Bang,
     List(Vec<Atom>),
     Generic(GenericMessage),
 }
 
+// Floats are compared and hashed by their bit pattern (see `Atom`).
+impl PartialEq for PdMessage {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (PdMessage::Float(a), PdMessage::Float(b)) => a.to_bits() == b.to_bits(),
+            (PdMessage::Symbol(a), PdMessage::Symbol(b)) => a == b,
+            (PdMessage::Bang, PdMessage::Bang) => true,
+            (PdMessage::List(a), PdMessage::List(b)) => a == b,
+            (PdMessage::Generic(a), PdMessage::Generic(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for PdMessage {}
+
+impl Hash for PdMessage {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            PdMessage::Float(f) => f.to_bits().hash(state),
+            PdMessage::Symbol(word) => word.hash(state),
+            PdMessage::Bang => (),
+            PdMessage::List(atoms) => atoms.hash(state),
+            PdMessage::Generic(msg) => msg.hash(state),
+        }
+    }
+}
+
+/// A message with a canonical (total) ordering, e.g. to sort received
+/// messages for deterministic test assertions.
+///
+/// Messages are ordered by type first (bang < float < symbol < list < generic),
+/// then by value: floats numerically (see `f32::total_cmp`, so `-0.0 < 0.0`),
+/// symbols and selectors lexicographically, atoms element-wise (numbers before symbols).
+///
+/// # Examples
+/// ```rust
+/// use fudi_rs::{CanonicalOrder, PdMessage};
+///
+/// let mut msgs = vec![CanonicalOrder(PdMessage::Float(2.0)), CanonicalOrder(PdMessage::Bang)];
+/// msgs.sort();
+/// assert!(msgs[0].0.is_bang());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalOrder(pub PdMessage);
+
+impl Ord for CanonicalOrder {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.canonical_cmp(&other.0)
+    }
+}
+
+impl PartialOrd for CanonicalOrder {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Compare atoms in canonical order (numbers before symbols, an int before an equal float).
+fn canonical_atom_cmp(a: &Atom, b: &Atom) -> Ordering {
+    match (a, b) {
+        (Atom::Float(a), Atom::Float(b)) => a.total_cmp(b),
+        (Atom::Int(a), Atom::Int(b)) => a.cmp(b),
+        (Atom::Int(a), Atom::Float(b)) => {
+            (*a as f64).total_cmp(&f64::from(*b)).then(Ordering::Less)
+        }
+        (Atom::Float(a), Atom::Int(b)) => f64::from(*a)
+            .total_cmp(&(*b as f64))
+            .then(Ordering::Greater),
+        (Atom::Symbol(a), Atom::Symbol(b)) => a.cmp(b),
+        (Atom::Symbol(_), _) => Ordering::Greater,
+        (_, Atom::Symbol(_)) => Ordering::Less,
+    }
+}
+
+// Compare sequences of atoms element-wise in canonical order.
+fn canonical_atoms_cmp(a: &[Atom], b: &[Atom]) -> Ordering {
+    for (a, b) in a.iter().zip(b.iter()) {
+        let ord = canonical_atom_cmp(a, b);
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+/// Line ending appended after the terminating semicolon of a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// A newline (i.e. `\n`) like vanilla Pure Data.
+    Lf,
+    /// A carriage return and a newline (i.e. `\r\n`).
+    CrLf,
+    /// Nothing after the semicolon.
+    None,
+}
+
+impl LineEnding {
+    fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+            LineEnding::None => "",
+        }
+    }
+}
+
+/// Options controlling how messages are serialized.
+///
+/// # Examples
+/// Separate the atoms with tabs instead of spaces.
+/// ```rust
+/// let options = fudi_rs::SerializeOptions {
+///     separator: '\t',
+///     ..Default::default()
+/// };
+/// let msg = fudi_rs::PdMessage::Float(1.5);
+/// assert_eq!("float\t1.5;\n", msg.to_text_with(&options));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerializeOptions {
+    /// Character separating the atoms (default: space). FUDI treats spaces and tabs alike.
+    /// Other characters (e.g. a comma) are only understood by non-vanilla receivers.
+    pub separator: char,
+    /// Character terminating the message (default: semicolon).
+    pub terminator: char,
+    /// Line ending after the terminating character (default: `LineEnding::Lf`).
+    pub line_ending: LineEnding,
+}
+
+impl Default for SerializeOptions {
+    fn default() -> Self {
+        SerializeOptions {
+            separator: ' ',
+            terminator: ';',
+            line_ending: LineEnding::Lf,
+        }
+    }
+}
+
 impl PdMessage {
     /// Generate a message string for the (given) message type.
     /// # note
     /// A message needs a trailing newline (i.e. '\n') according to the Java example in the [old wiki page](https://web.archive.org/web/20120304071510/http://wiki.puredata.info/en/FUDI). This is not explicitly mentioned in the FUDI specification.
-    fn to_text(&self) -> String {
-        let mut payload: String;
+    pub fn to_text(&self) -> String {
+        self.to_text_with(&SerializeOptions::default())
+    }
+
+    /// Generate a message string for the (given) message type using the given options.
+    ///
+    /// # Arguments
+    /// * `options` - options controlling the serialization
+    pub fn to_text_with(&self, options: &SerializeOptions) -> String {
+        let mut payload = String::with_capacity(self.size_hint(options));
+        self.write_text(&mut payload, options).unwrap();
+        payload
+    }
+
+    /// Append the serialized message to the buffer, so one buffer
+    /// can be reused for many messages.
+    ///
+    /// # Arguments
+    /// * `buf` - buffer to append the message to
+    pub fn write_to(&self, buf: &mut Vec<u8>) {
+        let options = SerializeOptions::default();
+        buf.reserve(self.size_hint(&options));
+        self.write_text(&mut ByteWriter(buf), &options).unwrap();
+    }
+
+    /// Generate the message bytes for the (given) message type.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        self.write_to(&mut buf);
+        buf
+    }
+
+    /// Get the number of bytes of the serialized message (see `to_bytes`)
+    /// without serializing it.
+    pub fn serialized_len(&self) -> usize {
+        let mut counter = ByteCounter(0);
+        self.write_text(&mut counter, &SerializeOptions::default())
+            .unwrap();
+        counter.0
+    }
+
+    // Estimate the length of the serialized message.
+    fn size_hint(&self, options: &SerializeOptions) -> usize {
+        let sep = options.separator.len_utf8();
+        let atoms = match self {
+            PdMessage::Float(_) | PdMessage::Bang => 16,
+            PdMessage::Symbol(word) => word.as_str().len() + sep + 8,
+            PdMessage::List(items) => items.len() * (sep + 8) + 4,
+            PdMessage::Generic(msg) => {
+                let atoms = msg.atoms.iter().map(|atom| match atom {
+                    Atom::Symbol(word) => word.len() + sep,
+                    _ => sep + 8,
+                });
+                atoms.sum::<usize>() + msg.selector.len()
+            }
+        };
+        atoms + 3
+    }
+
+    // Write the serialized message to the writer.
+    fn write_text<W: fmt::Write>(
+        &self,
+        payload: &mut W,
+        options: &SerializeOptions,
+    ) -> fmt::Result {
+        let sep = options.separator;
         match &self {
-            PdMessage::Float(f) => payload = format!("float {}", f),
-            PdMessage::Symbol(word) => payload = format!("symbol {}", word),
-            PdMessage::Bang => payload = String::from("bang"),
+            PdMessage::Float(f) => write!(payload, "float{}{}", sep, f)?,
+            PdMessage::Symbol(word) => write!(payload, "symbol{}{}", sep, word)?,
+            PdMessage::Bang => payload.write_str("bang")?,
             PdMessage::List(items) => {
-                payload = String::from("list");
-                for atom in items.iter() {}
+                payload.write_str("list")?;
+                for atom in items.iter() {
+                    write!(payload, "{}{}", sep, atom)?;
+                }
             }
             PdMessage::Generic(msg) => {
-                payload = msg.selector.clone();
+                payload.write_str(&msg.selector)?;
                 for atom in msg.atoms.iter() {
-                    payload = payload + " " + atom;
+                    write!(payload, "{}{}", sep, atom)?;
                 }
             }
         }
-        payload = format!("{};\n", payload); // newline not in spec, but in vanilla pd
-        payload
+        payload.write_char(options.terminator)?;
+        payload.write_str(options.line_ending.as_str()) // newline not in spec, but in vanilla pd
+    }
+
+    /// Create a message from a sequence of atoms like Pure Data interprets it:
+    /// no atoms are a bang, a single float or symbol is a float or symbol message,
+    /// several atoms starting with a float are a list, otherwise the first
+    /// symbol is the selector of a generic message.
+    ///
+    /// # Arguments
+    /// * `atoms` - the atoms of the message
+    pub fn from_atoms(atoms: Vec<Atom>) -> PdMessage {
+        match atoms.as_slice() {
+            [] => return PdMessage::Bang,
+            [Atom::Float(f)] => return PdMessage::Float(*f),
+            [Atom::Int(i)] => return PdMessage::Float(*i as f32),
+            [Atom::Symbol(word)] => {
+                let symbol = Symbol::new(word).unwrap_or_else(|| Symbol::escaped(word));
+                return PdMessage::Symbol(symbol);
+            }
+            [Atom::Float(_), ..] | [Atom::Int(_), ..] => return PdMessage::List(atoms),
+            _ => (),
+        }
+
+        // several atoms starting with a symbol
+        let mut atoms = atoms.into_iter();
+        let selector = match atoms.next() {
+            Some(Atom::Symbol(word)) => word,
+            _ => unreachable!(),
+        };
+        PdMessage::Generic(GenericMessage {
+            selector,
+            atoms: atoms.collect(),
+        })
+    }
+
+    /// Generate a hex and ASCII dump of the serialized message (see `hex_dump`).
+    pub fn hex_dump(&self) -> String {
+        hex_dump(&self.to_bytes())
+    }
+
+    /// Compare two messages in canonical order (see `CanonicalOrder`),
+    /// e.g. to sort a `Vec<PdMessage>` with `sort_by`.
+    ///
+    /// # Arguments
+    /// * `other` - message to compare with
+    pub fn canonical_cmp(&self, other: &PdMessage) -> Ordering {
+        fn rank(msg: &PdMessage) -> u8 {
+            match msg {
+                PdMessage::Bang => 0,
+                PdMessage::Float(_) => 1,
+                PdMessage::Symbol(_) => 2,
+                PdMessage::List(_) => 3,
+                PdMessage::Generic(_) => 4,
+            }
+        }
+        match (self, other) {
+            (PdMessage::Float(a), PdMessage::Float(b)) => a.total_cmp(b),
+            (PdMessage::Symbol(a), PdMessage::Symbol(b)) => a.as_str().cmp(b.as_str()),
+            (PdMessage::List(a), PdMessage::List(b)) => canonical_atoms_cmp(a, b),
+            (PdMessage::Generic(a), PdMessage::Generic(b)) => a
+                .selector
+                .cmp(&b.selector)
+                .then_with(|| canonical_atoms_cmp(&a.atoms, &b.atoms)),
+            _ => rank(self).cmp(&rank(other)),
+        }
+    }
+
+    /// Get the value of a float message.
+    pub fn as_float(&self) -> Option<f32> {
+        match self {
+            PdMessage::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    /// Get the value of a symbol message.
+    pub fn as_symbol(&self) -> Option<&str> {
+        match self {
+            PdMessage::Symbol(word) => Some(word.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Get the selector of the message (i.e. `float`, `symbol`, `bang`, `list`,
+    /// or the selector of a generic message), e.g. for routing messages.
+    pub fn selector(&self) -> &str {
+        match self {
+            PdMessage::Float(_) => "float",
+            PdMessage::Symbol(_) => "symbol",
+            PdMessage::Bang => "bang",
+            PdMessage::List(_) => "list",
+            PdMessage::Generic(msg) => &msg.selector,
+        }
+    }
+
+    /// Test for the message being a bang.
+    pub fn is_bang(&self) -> bool {
+        matches!(self, PdMessage::Bang)
+    }
+
+    /// Create a float message, rejecting NaN and infinity.
+    ///
+    /// # Arguments
+    /// * `f` - value of the message
+    pub fn try_float(f: f32) -> Option<PdMessage> {
+        if f.is_finite() {
+            Some(PdMessage::Float(f))
+        } else {
+            None
+        }
+    }
+
+    // Test whether all floats of the message are neither NaN nor infinite.
+    fn is_finite(&self) -> bool {
+        let atoms = match self {
+            PdMessage::Float(f) => return f.is_finite(),
+            PdMessage::List(atoms) => atoms,
+            PdMessage::Generic(msg) => &msg.atoms,
+            PdMessage::Symbol(_) | PdMessage::Bang => return true,
+        };
+        atoms.iter().all(|atom| match atom {
+            Atom::Float(f) => f.is_finite(),
+            _ => true,
+        })
+    }
+}
+
+// Create float messages from numbers (e.g. MIDI values).
+// Whole numbers are serialized without a fractional part (e.g. `float 60;`).
+macro_rules! impl_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for PdMessage {
+                fn from(value: $t) -> Self {
+                    PdMessage::Float(value as f32)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(i8, u8, i16, u16, i32, u32, f32);
+
+// Adapter appending formatted text to a byte buffer.
+struct ByteWriter<'a>(&'a mut Vec<u8>);
+
+impl fmt::Write for ByteWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+// Adapter counting the bytes of formatted text.
+struct ByteCounter(usize);
+
+impl fmt::Write for ByteCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
     }
 }
 
+/// Generate a hex and ASCII dump of bytes (like `hexdump -C`), e.g. to
+/// inspect what is sent or received on the wire. Every line shows the
+/// offset, up to 16 bytes in hex, and their printable ASCII characters.
+///
+/// # Examples
+/// ```rust
+/// assert_eq!(
+///     "00000000  62 61 6e 67 3b 0a                                |bang;.|\n",
+///     fudi_rs::hex_dump(b"bang;\n")
+/// );
+/// ```
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut dump = String::new();
+    for (i, line) in bytes.chunks(16).enumerate() {
+        let hex: Vec<String> = line.iter().map(|b| format!("{:02x}", b)).collect();
+        let ascii: String = line
+            .iter()
+            .map(|&b| {
+                if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        dump.push_str(&format!(
+            "{:08x}  {:<47}  |{}|\n",
+            i * 16,
+            hex.join(" "),
+            ascii
+        ));
+    }
+    dump
+}
+
+/// Serialize several messages into a single FUDI payload
+/// (e.g. to write them to a file or a TCP stream at once).
+///
+/// # Examples
+/// ```rust
+/// use fudi_rs::PdMessage;
+///
+/// let payload = fudi_rs::serialize_batch(&[PdMessage::Bang, PdMessage::Float(1.0)]);
+/// assert_eq!(b"bang;\nfloat 1;\n".to_vec(), payload);
+/// ```
+pub fn serialize_batch(msgs: &[PdMessage]) -> Vec<u8> {
+    let mut payload = vec![];
+    for msg in msgs.iter() {
+        msg.write_to(&mut payload);
+    }
+    payload
+}
+
 #[cfg(test)]
 mod test_pdmessage {
     use super::*;
 
+    fn sym(word: &str) -> Atom {
+        Atom::Symbol(String::from(word))
+    }
+
     #[test]
     fn generate_float_message() {
         let msg = PdMessage::Float(2.974);
@@ -110,7 +757,7 @@ mod test_pdmessage {
 
     #[test]
     fn generate_symbol_message() {
-        let msg = PdMessage::Symbol(String::from("foobar"));
+        let msg = PdMessage::Symbol(Symbol::escaped("foobar"));
         assert_eq!(String::from("symbol foobar;\n"), msg.to_text());
     }
 
@@ -124,122 +771,1377 @@ mod test_pdmessage {
     fn generate_generic_message() {
         let msg = PdMessage::Generic(GenericMessage {
             selector: String::from("selector"),
-            atoms: vec!["one".to_string(), "two".to_string(), "17.9".to_string()],
+            atoms: vec![sym("one"), sym("two"), Atom::Float(17.9)],
         });
         assert_eq!(String::from("selector one two 17.9;\n"), msg.to_text());
     }
-}
-
-/// Encapsulate sending Pure Date messages via FUDI over UDP.
-/// This is the library equivalent of the netsend-object for UDP.
-///
-/// # references
-/// * [FLOSS manuals: Pure Data - send and receive](http://write.flossmanuals.net/pure-data/send-and-receive/)
-pub struct NetSendUdp {
-    target: SocketAddr,
-    socket: UdpSocket,
-}
-
-impl NetSendUdp {
-    /// Create a new instance and set target address.
-    ///
-    /// # Arguments
-    /// * `target` - target host (& port) to send messages to
-    pub fn new(target: &str) -> crate::NetSendUdp {
-        NetSendUdp {
-            target: SocketAddr::from_str(target).expect("failed to parse target address"),
-            socket: UdpSocket::bind("0.0.0.0:0").expect("failed to bind host socket"),
-        }
-    }
 
-    /// Send a message to the target and return the number of bytes sent.
-    ///
-    /// # Arguments
-    /// * `msg` - message to send to the target
-    pub fn send(&self, msg: &PdMessage) -> Result<usize> {
-        self.socket.send_to(msg.to_text().as_bytes(), self.target)
+    #[test]
+    fn custom_message_round_trip() {
+        let msg = PdMessage::Generic(GenericMessage::new(
+            "myobj",
+            vec![Atom::Int(1), sym("foo"), Atom::Float(2.5)],
+        ));
+        assert_eq!("myobj 1 foo 2.5;\n", msg.to_text());
+        assert_eq!(Ok(msg), get_message(b"myobj 1 foo 2.5;\n"));
     }
-}
-
-#[cfg(test)]
-mod test_netsendudp {
-    use super::*;
 
     #[test]
-    fn create_udp_netsend_test_target() {
-        let target = "127.0.0.1:8989";
-        let ns = NetSendUdp::new(&String::from(target));
+    fn non_finite_floats() {
+        assert_eq!("float inf;\n", PdMessage::Float(f32::INFINITY).to_text());
+        assert_eq!(
+            "float -inf;\n",
+            PdMessage::Float(f32::NEG_INFINITY).to_text()
+        );
+        assert_eq!("float NaN;\n", PdMessage::Float(f32::NAN).to_text());
+        // the text is read back like Pure Data does (i.e. as symbol)
+        let res = get_message(b"float inf;\n");
+        assert_ne!(Ok(PdMessage::Float(f32::INFINITY)), res);
+        assert!(res.map_or(true, |msg| msg.as_float().is_none()));
 
-        assert_eq!(ns.target.is_ipv4(), true);
-        assert_eq!(ns.target.ip(), IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)));
-        assert_eq!(ns.target.port(), 8989);
+        assert_eq!(Some(PdMessage::Float(1.5)), PdMessage::try_float(1.5));
+        assert_eq!(None, PdMessage::try_float(f32::INFINITY));
+        assert_eq!(None, PdMessage::try_float(f32::NAN));
+        assert!(!PdMessage::List(vec![Atom::Float(f32::NAN)]).is_finite());
     }
 
     #[test]
-    fn send_bang_into_ether() {
-        let msg = PdMessage::Bang;
-        let target = "127.0.0.1:8989";
-        let ns = NetSendUdp::new(&String::from(target));
-        let res = ns.send(&msg);
-        match res {
-            Ok(bsend) => assert_eq!(bsend, 6),
-            Err(fail) => panic!(fail),
-        }
+    fn iterate_generic_message_atoms() {
+        let msg = match get_message(b"note 60 127;\n") {
+            Ok(PdMessage::Generic(msg)) => msg,
+            res => panic!("generic message expected, got {:?}", res),
+        };
+        assert_eq!("note", msg.selector());
+        let values: Vec<Option<f32>> = msg.atoms_iter().map(Atom::as_float).collect();
+        assert_eq!(vec![Some(60.0), Some(127.0)], values);
+        assert_eq!(2, msg.atoms_iter().count());
     }
 
     #[test]
-    fn send_float_into_ether() {
-        let msg = PdMessage::Float(432.0);
+    fn access_message_values() {
+        assert_eq!(Some(3.0), PdMessage::Float(3.0).as_float());
+        assert_eq!(None, PdMessage::Bang.as_float());
+
+        let msg = PdMessage::Symbol(Symbol::escaped("foo"));
+        assert_eq!(Some("foo"), msg.as_symbol());
+        assert_eq!(None, PdMessage::Float(3.0).as_symbol());
+
+        assert!(PdMessage::Bang.is_bang());
+        assert!(!msg.is_bang());
+    }
+
+    #[test]
+    fn generate_tab_separated_generic_message() {
+        let msg = PdMessage::Generic(GenericMessage {
+            selector: String::from("selector"),
+            atoms: vec![sym("one"), sym("two"), Atom::Float(17.9)],
+        });
+        let options = SerializeOptions {
+            separator: '\t',
+            ..Default::default()
+        };
+        assert_eq!(
+            String::from("selector\tone\ttwo\t17.9;\n"),
+            msg.to_text_with(&options)
+        );
+        assert_eq!(
+            String::from("selector one two 17.9;\n"),
+            msg.to_text_with(&SerializeOptions::default())
+        );
+    }
+
+    #[test]
+    fn generate_large_generic_message() {
+        let msg = PdMessage::Generic(GenericMessage {
+            selector: String::from("selector"),
+            atoms: (0..1000).map(Atom::Int).collect(),
+        });
+        let text = msg.to_text();
+
+        let mut expected = String::from("selector");
+        for i in 0..1000 {
+            expected = format!("{} {}", expected, i);
+        }
+        assert_eq!(format!("{};\n", expected), text);
+    }
+
+    #[test]
+    fn generate_symbol_message_with_space() {
+        assert!(Symbol::new("foo bar").is_none());
+        let msg = PdMessage::Symbol(Symbol::escaped("foo bar"));
+        assert_eq!("symbol foo\\ bar;\n", msg.to_text());
+        let atoms = parse_atoms(msg.to_text().as_bytes()).unwrap();
+        assert_eq!(2, atoms.len());
+    }
+
+    #[test]
+    fn hex_dump_of_messages() {
+        assert_eq!(
+            "00000000  62 61 6e 67 3b 0a                                |bang;.|\n",
+            PdMessage::Bang.hex_dump()
+        );
+        let dump = hex_dump(b"symbol abcdefghijk;\n");
+        let lines: Vec<&str> = dump.lines().collect();
+        assert_eq!(2, lines.len());
+        assert!(lines[0].starts_with("00000000  73 79 6d 62 6f 6c 20 61"));
+        assert!(lines[1].starts_with("00000010  6a 6b 3b 0a"));
+        assert!(hex_dump(b"").is_empty());
+    }
+
+    #[test]
+    fn escaped_symbol_atom() {
+        let atom = Atom::symbol_escaped("a b c");
+        assert_eq!(Atom::Symbol(String::from("a\\ b\\ c")), atom);
+        let msg = PdMessage::List(vec![Atom::Float(1.0), atom]);
+        assert_eq!("list 1 a\\ b\\ c;\n", msg.to_text());
+        let atoms = parse_atoms(msg.to_text().as_bytes()).expect("parsing atoms failed");
+        assert_eq!(3, atoms.len());
+    }
+
+    #[test]
+    fn message_from_atoms() {
+        assert!(PdMessage::from_atoms(vec![]).is_bang());
+        let msg = PdMessage::from_atoms(vec![Atom::Float(3.0)]);
+        assert_eq!(Some(3.0), msg.as_float());
+        let msg = PdMessage::from_atoms(vec![Atom::Symbol(String::from("foo"))]);
+        assert_eq!(Some("foo"), msg.as_symbol());
+        let msg = PdMessage::from_atoms(vec![Atom::Float(1.0), Atom::Symbol(String::from("a"))]);
+        assert_eq!("list 1 a;\n", msg.to_text());
+        let msg = PdMessage::from_atoms(vec![
+            Atom::Symbol(String::from("note")),
+            Atom::Float(60.0),
+            Atom::Float(127.0),
+        ]);
+        assert_eq!("note", msg.selector());
+        assert_eq!("note 60 127;\n", msg.to_text());
+    }
+
+    #[test]
+    fn message_selectors() {
+        assert_eq!("float", PdMessage::Float(1.0).selector());
+        assert_eq!("symbol", PdMessage::Symbol(Symbol::escaped("a")).selector());
+        assert_eq!("bang", PdMessage::Bang.selector());
+        assert_eq!("list", PdMessage::List(vec![]).selector());
+        let msg = PdMessage::Generic(GenericMessage {
+            selector: String::from("note"),
+            atoms: vec![Atom::Int(60)],
+        });
+        assert_eq!("note", msg.selector());
+    }
+
+    #[test]
+    fn float_message_from_numbers() {
+        assert_eq!("float 60;\n", PdMessage::from(60i32).to_text());
+        assert_eq!("float 127;\n", PdMessage::from(127u8).to_text());
+        assert_eq!("float -3;\n", PdMessage::from(-3i16).to_text());
+        assert_eq!("float 0.5;\n", PdMessage::from(0.5f32).to_text());
+    }
+
+    #[test]
+    fn write_messages_into_buffer() {
+        let mut buf = vec![];
+        PdMessage::Bang.write_to(&mut buf);
+        PdMessage::Float(7.5).write_to(&mut buf);
+        assert_eq!(b"bang;\nfloat 7.5;\n".to_vec(), buf);
+    }
+
+    #[test]
+    fn length_of_serialized_messages() {
+        let msgs = [
+            PdMessage::Float(-12.25),
+            PdMessage::Symbol(Symbol::escaped("café au lait")),
+            PdMessage::Bang,
+            PdMessage::List(vec![Atom::Float(1.0), Atom::Symbol(String::from("foo"))]),
+            PdMessage::Generic(GenericMessage {
+                selector: String::from("selector"),
+                atoms: vec![sym("one"), sym("two")],
+            }),
+        ];
+        for msg in msgs.iter() {
+            assert_eq!(msg.to_bytes().len(), msg.serialized_len());
+        }
+    }
+
+    #[test]
+    fn serialize_message_batch() {
+        let msgs = [
+            PdMessage::Bang,
+            PdMessage::Float(3.0),
+            PdMessage::Symbol(Symbol::escaped("foo")),
+        ];
+        assert_eq!(
+            b"bang;\nfloat 3;\nsymbol foo;\n".to_vec(),
+            serialize_batch(&msgs)
+        );
+        assert!(serialize_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn sort_messages_canonically() {
+        let mut msgs = vec![
+            PdMessage::Generic(GenericMessage {
+                selector: String::from("b"),
+                atoms: vec![],
+            }),
+            PdMessage::Symbol(Symbol::escaped("b")),
+            PdMessage::Float(2.0),
+            PdMessage::List(vec![Atom::Float(1.0), Atom::Symbol(String::from("a"))]),
+            PdMessage::Symbol(Symbol::escaped("a")),
+            PdMessage::Bang,
+            PdMessage::Float(-1.0),
+            PdMessage::List(vec![Atom::Float(1.0)]),
+            PdMessage::Generic(GenericMessage {
+                selector: String::from("a"),
+                atoms: vec![sym("x")],
+            }),
+        ];
+        msgs.sort_by(PdMessage::canonical_cmp);
+        let texts: Vec<String> = msgs.iter().map(|msg| msg.to_text()).collect();
+        assert_eq!(
+            vec![
+                "bang;\n",
+                "float -1;\n",
+                "float 2;\n",
+                "symbol a;\n",
+                "symbol b;\n",
+                "list 1;\n",
+                "list 1 a;\n",
+                "a x;\n",
+                "b;\n"
+            ],
+            texts
+        );
+
+        let mut wrapped: Vec<CanonicalOrder> = msgs.into_iter().rev().map(CanonicalOrder).collect();
+        wrapped.sort();
+        assert!(wrapped[0].0.is_bang());
+    }
+
+    #[test]
+    fn deduplicate_messages() {
+        use std::collections::HashSet;
+
+        let mut set = HashSet::new();
+        set.insert(PdMessage::Symbol(Symbol::escaped("a")));
+        set.insert(PdMessage::Symbol(Symbol::escaped("a")));
+        assert_eq!(1, set.len());
+        set.insert(PdMessage::Bang);
+        set.insert(PdMessage::Float(1.0));
+        set.insert(PdMessage::Float(1.0));
+        assert_eq!(3, set.len());
+        assert_ne!(PdMessage::Float(0.0), PdMessage::Float(-0.0));
+    }
+
+    #[test]
+    fn generate_comma_separated_generic_message() {
+        let msg = PdMessage::Generic(GenericMessage {
+            selector: String::from("selector"),
+            atoms: vec![sym("one"), sym("two")],
+        });
+        let options = SerializeOptions {
+            separator: ',',
+            ..Default::default()
+        };
+        assert_eq!("selector,one,two;\n", msg.to_text_with(&options));
+        let options = SerializeOptions {
+            separator: ',',
+            terminator: '.',
+            line_ending: LineEnding::None,
+        };
+        assert_eq!("selector,one,two.", msg.to_text_with(&options));
+    }
+
+    #[test]
+    fn generate_message_with_line_endings() {
+        let msg = PdMessage::Float(2.0);
+        let mut options = SerializeOptions::default();
+        assert_eq!("float 2;\n", msg.to_text_with(&options));
+        options.line_ending = LineEnding::CrLf;
+        assert_eq!("float 2;\r\n", msg.to_text_with(&options));
+        options.line_ending = LineEnding::None;
+        assert_eq!("float 2;", msg.to_text_with(&options));
+    }
+
+    #[test]
+    fn specialize_generic_message() {
+        let msg = GenericMessage::new("float", vec![Atom::Int(3)]);
+        assert_eq!(PdMessage::Float(3.0), msg.specialize());
+        let msg = GenericMessage::new("symbol", vec![sym("foo")]);
+        assert_eq!(
+            PdMessage::Symbol(Symbol(String::from("foo"))),
+            msg.specialize()
+        );
+        assert_eq!(
+            PdMessage::Bang,
+            GenericMessage::new("bang", vec![]).specialize()
+        );
+        // lists are coerced like get_message does
+        let msg = GenericMessage::new("list", vec![Atom::Int(5)]);
+        assert_eq!(PdMessage::Float(5.0), msg.specialize());
+        let msg = GenericMessage::new("list", vec![]);
+        assert_eq!(PdMessage::Bang, msg.specialize());
+        let msg = GenericMessage::new("list", vec![Atom::Int(1), sym("a")]);
+        assert_eq!(
+            PdMessage::List(vec![Atom::Int(1), sym("a")]),
+            msg.specialize()
+        );
+
+        let msg = GenericMessage::new("float", vec![sym("foo")]);
+        assert_eq!(PdMessage::Generic(msg.clone()), msg.specialize());
+        let msg = GenericMessage::new("symbol", vec![Atom::Int(1)]);
+        assert_eq!(PdMessage::Generic(msg.clone()), msg.specialize());
+        let msg = GenericMessage::new("note", vec![Atom::Int(60)]);
+        assert_eq!(PdMessage::Generic(msg.clone()), msg.specialize());
+    }
+}
+
+/// Resolve a host (& port) to a single address, preferring IPv4 addresses
+/// if a hostname resolves to several addresses.
+fn resolve(addr: &str) -> Result<SocketAddr> {
+    let addrs: Vec<SocketAddr> = addr.to_socket_addrs()?.collect();
+    match addrs.iter().find(|a| a.is_ipv4()).or_else(|| addrs.first()) {
+        Some(addr) => Ok(*addr),
+        None => Err(Error::new(
+            ErrorKind::AddrNotAvailable,
+            "could not resolve address",
+        )),
+    }
+}
+
+/// Errors encountered while sending a message.
+#[derive(Debug)]
+pub enum SendError {
+    /// The underlying socket reported an error.
+    Io(Error),
+    /// The serialized message is not a single well-formed FUDI message
+    /// (e.g. a symbol containing an unescaped semicolon).
+    InvalidMessage(ParseError),
+    /// Fewer bytes than the serialized message were sent.
+    ShortWrite { sent: usize, expected: usize },
+    /// The serialized message does not fit into a single datagram.
+    TooLarge { len: usize, max: usize },
+    /// The message contains a NaN or infinite float.
+    NonFiniteFloat,
+}
+
+impl fmt::Display for SendError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SendError::Io(err) => write!(f, "sending failed: {}", err),
+            SendError::InvalidMessage(err) => write!(f, "invalid message: {}", err),
+            SendError::ShortWrite { sent, expected } => {
+                write!(f, "short write: sent {} of {} bytes", sent, expected)
+            }
+            SendError::TooLarge { len, max } => {
+                write!(f, "message too large: {} bytes (maximum {})", len, max)
+            }
+            SendError::NonFiniteFloat => f.write_str("message contains NaN or infinity"),
+        }
+    }
+}
+
+impl std::error::Error for SendError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SendError::Io(err) => Some(err),
+            SendError::InvalidMessage(err) => Some(err),
+            SendError::ShortWrite { .. }
+            | SendError::TooLarge { .. }
+            | SendError::NonFiniteFloat => None,
+        }
+    }
+}
+
+impl From<Error> for SendError {
+    fn from(err: Error) -> SendError {
+        SendError::Io(err)
+    }
+}
+
+// Check that a serialized message fits into a single datagram.
+fn check_udp_size(len: usize) -> std::result::Result<(), SendError> {
+    if len > MAX_UDP_PAYLOAD {
+        return Err(SendError::TooLarge {
+            len,
+            max: MAX_UDP_PAYLOAD,
+        });
+    }
+    Ok(())
+}
+
+// Check that a serialized payload is exactly one well-formed message. The limits
+// guarding against untrusted input do not apply to the messages sent.
+fn validate_single(payload: &[u8]) -> std::result::Result<(), SendError> {
+    let unlimited = ParserLimits {
+        max_atoms: usize::MAX,
+        max_message_bytes: usize::MAX,
+        max_messages: usize::MAX,
+    };
+    validate_with(payload, &unlimited).map_err(SendError::InvalidMessage)?;
+    // validate succeeded -> there is a terminator
+    let end = parser::find_terminator(payload).unwrap();
+    if !payload[end + 1..].iter().all(u8::is_ascii_whitespace) {
+        return Err(SendError::InvalidMessage(ParseError::Malformed));
+    }
+    Ok(())
+}
+
+/// Encapsulate sending Pure Date messages via FUDI over UDP.
+/// This is the library equivalent of the netsend-object for UDP.
+///
+/// The sender is `Send` and `Sync` and all send methods take `&self`,
+/// so one instance can be shared between threads (e.g. of a thread pool)
+/// by wrapping it in an `Arc`. Each message is sent as a datagram of its own.
+///
+/// # Examples
+/// ```rust
+/// use std::sync::Arc;
+/// let netsend = Arc::new(fudi_rs::NetSendUdp::new("127.0.0.1:5678"));
+/// let shared = Arc::clone(&netsend);
+/// std::thread::spawn(move || shared.bang()).join().unwrap().unwrap();
+/// ```
+///
+/// # references
+/// * [FLOSS manuals: Pure Data - send and receive](http://write.flossmanuals.net/pure-data/send-and-receive/)
+pub struct NetSendUdp {
+    target: SocketAddr,
+    socket: UdpSocket,
+}
+
+impl NetSendUdp {
+    /// Create a new instance and set target address.
+    ///
+    /// # Arguments
+    /// * `target` - target host (& port) to send messages to
+    pub fn new(target: &str) -> crate::NetSendUdp {
+        NetSendUdp::try_new(target).expect("failed to create netsend")
+    }
+
+    /// Create a new instance and set target address, returning an error
+    /// if the target can not be resolved. IPv4 addresses are preferred
+    /// if a hostname resolves to several addresses.
+    ///
+    /// # Arguments
+    /// * `target` - target host (& port) to send messages to
+    pub fn try_new(target: &str) -> Result<crate::NetSendUdp> {
+        NetSendUdp::from_addr(resolve(target)?)
+    }
+
+    /// Create a new instance from a target IP address and port.
+    ///
+    /// # Arguments
+    /// * `ip` - IP address of the target host
+    /// * `port` - port on the target host to send messages to
+    pub fn new_ip(ip: IpAddr, port: u16) -> Result<crate::NetSendUdp> {
+        NetSendUdp::from_addr(SocketAddr::new(ip, port))
+    }
+
+    /// Create a new instance for an already resolved target address.
+    ///
+    /// # Arguments
+    /// * `target` - target address to send messages to
+    pub fn from_addr(target: SocketAddr) -> Result<crate::NetSendUdp> {
+        let host = match target {
+            SocketAddr::V4(_) => "0.0.0.0:0",
+            SocketAddr::V6(_) => "[::]:0",
+        };
+        Ok(NetSendUdp {
+            target,
+            socket: UdpSocket::bind(host)?,
+        })
+    }
+
+    /// Create a new instance sending via an existing socket
+    /// (e.g. one managed by an event loop).
+    ///
+    /// # Arguments
+    /// * `socket` - bound socket to send messages with
+    /// * `target` - target address to send messages to
+    pub fn from_socket(socket: UdpSocket, target: SocketAddr) -> crate::NetSendUdp {
+        NetSendUdp { target, socket }
+    }
+
+    /// Switch the socket into (or out of) non-blocking mode. In non-blocking
+    /// mode `send` returns a `WouldBlock` error instead of waiting for space
+    /// in a full send buffer.
+    ///
+    /// # Arguments
+    /// * `nonblocking` - return immediately if the message can not be sent
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        self.socket.set_nonblocking(nonblocking)
+    }
+
+    /// Send a message to the target without blocking and return the number
+    /// of bytes sent. A full send buffer is reported as `WouldBlock` error.
+    ///
+    /// Only this send is non-blocking (i.e. `MSG_DONTWAIT`), the mode of the
+    /// socket (see `set_nonblocking`) is left alone, so other threads sharing
+    /// the instance are not affected.
+    ///
+    /// *note*: On platforms without `MSG_DONTWAIT` (e.g. Windows) this is a
+    /// regular `send`, which blocks unless the socket is in non-blocking mode.
+    ///
+    /// # Arguments
+    /// * `msg` - message to send to the target
+    pub fn try_send(&self, msg: &PdMessage) -> Result<usize> {
+        if let Err(err) = check_udp_size(msg.serialized_len()) {
+            return Err(Error::new(ErrorKind::InvalidInput, err));
+        }
+        #[cfg(unix)]
+        return SockRef::from(&self.socket).send_to_with_flags(
+            &codec::encode(msg),
+            &self.target.into(),
+            libc::MSG_DONTWAIT,
+        );
+        #[cfg(not(unix))]
+        return self.send(msg);
+    }
+
+    /// Send a message to the target and return the number of bytes sent.
+    ///
+    /// A message exceeding `MAX_UDP_PAYLOAD` bytes is reported as
+    /// `SendError::TooLarge` (wrapped in an `InvalidInput` error)
+    /// without sending anything.
+    ///
+    /// # Arguments
+    /// * `msg` - message to send to the target
+    pub fn send(&self, msg: &PdMessage) -> Result<usize> {
+        if let Err(err) = check_udp_size(msg.serialized_len()) {
+            return Err(Error::new(ErrorKind::InvalidInput, err));
+        }
+        self.socket.send_to(&codec::encode(msg), self.target)
+    }
+
+    /// Send a bang message to the target and return the number of bytes sent.
+    pub fn bang(&self) -> Result<usize> {
+        self.send(&PdMessage::Bang)
+    }
+
+    /// Send a float message to the target and return the number of bytes sent.
+    ///
+    /// # Arguments
+    /// * `f` - value to send
+    pub fn float(&self, f: f32) -> Result<usize> {
+        self.send(&PdMessage::Float(f))
+    }
+
+    /// Send a symbol message to the target and return the number of bytes sent.
+    /// The symbol is escaped (see `Symbol::escaped`), so it is sent as one atom.
+    ///
+    /// # Arguments
+    /// * `word` - the unescaped symbol to send
+    pub fn symbol(&self, word: &str) -> Result<usize> {
+        self.send(&PdMessage::Symbol(Symbol::escaped(word)))
+    }
+
+    /// Send a message to the target and make sure it is a single well-formed
+    /// message without NaN or infinite floats and was sent completely
+    /// (i.e. in a single datagram).
+    ///
+    /// # Arguments
+    /// * `msg` - message to send to the target
+    pub fn send_checked(&self, msg: &PdMessage) -> std::result::Result<(), SendError> {
+        if !msg.is_finite() {
+            return Err(SendError::NonFiniteFloat);
+        }
+        let payload = msg.to_text();
+        check_udp_size(payload.len())?;
+        validate_single(payload.as_bytes())?;
+        let sent = self.socket.send_to(payload.as_bytes(), self.target)?;
+        if sent < payload.len() {
+            return Err(SendError::ShortWrite {
+                sent,
+                expected: payload.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Send pre-serialized bytes to the target verbatim and return
+    /// the number of bytes sent.
+    ///
+    /// *note*: The caller is responsible for correct framing (i.e. the
+    /// terminating semicolon) of the messages contained in the bytes.
+    ///
+    /// # Arguments
+    /// * `bytes` - payload to send to the target
+    pub fn send_raw(&self, bytes: &[u8]) -> Result<usize> {
+        self.socket.send_to(bytes, self.target)
+    }
+
+    /// Send several messages batched into as few datagrams as possible
+    /// and return the number of bytes sent.
+    ///
+    /// The messages are split into several datagrams if they exceed the
+    /// maximum UDP payload. A single message exceeding it is an error.
+    ///
+    /// # Arguments
+    /// * `msgs` - messages to send to the target
+    pub fn send_batch(&self, msgs: &[PdMessage]) -> Result<usize> {
+        let mut sent = 0;
+        let mut payload = vec![];
+        for msg in msgs.iter() {
+            let len = msg.serialized_len();
+            if let Err(err) = check_udp_size(len) {
+                return Err(Error::new(ErrorKind::InvalidInput, err));
+            }
+            if payload.len() + len > MAX_UDP_PAYLOAD {
+                sent += self.socket.send_to(&payload, self.target)?;
+                payload.clear();
+            }
+            msg.write_to(&mut payload);
+        }
+        if !payload.is_empty() {
+            sent += self.socket.send_to(&payload, self.target)?;
+        }
+        Ok(sent)
+    }
+
+    /// Send several copies of a message to the target and return the
+    /// total number of bytes sent.
+    ///
+    /// *note*: This is a best-effort heuristic to improve the odds of a message
+    /// getting through a lossy link, not reliable delivery. The receiver may
+    /// get any number of the copies (including none or all of them).
+    ///
+    /// # Arguments
+    /// * `msg` - message to send to the target
+    /// * `copies` - number of copies to send
+    /// * `interval` - pause between two consecutive copies
+    pub fn send_redundant(
+        &self,
+        msg: &PdMessage,
+        copies: usize,
+        interval: Duration,
+    ) -> Result<usize> {
+        if let Err(err) = check_udp_size(msg.serialized_len()) {
+            return Err(Error::new(ErrorKind::InvalidInput, err));
+        }
+        let payload = msg.to_text();
+        let mut sent = 0;
+        for i in 0..copies {
+            if i > 0 {
+                thread::sleep(interval);
+            }
+            sent += self.socket.send_to(payload.as_bytes(), self.target)?;
+        }
+        Ok(sent)
+    }
+
+    /// Set the size of the operating system send buffer (i.e. `SO_SNDBUF`).
+    /// A larger buffer helps avoiding dropped packets when sending at high rates.
+    ///
+    /// *note*: The operating system may adjust the size. Linux doubles the
+    /// requested value and enforces limits (see `net.core.wmem_max`).
+    ///
+    /// # Arguments
+    /// * `size` - requested buffer size (in bytes)
+    pub fn set_send_buffer_size(&self, size: usize) -> Result<()> {
+        SockRef::from(&self.socket).set_send_buffer_size(size)
+    }
+
+    /// Get the size of the operating system send buffer (i.e. `SO_SNDBUF`).
+    pub fn send_buffer_size(&self) -> Result<usize> {
+        SockRef::from(&self.socket).send_buffer_size()
+    }
+
+    /// Set the type-of-service byte (i.e. `IP_TOS`) of outgoing packets to
+    /// mark them for prioritized delivery (e.g. `0xb8` for DSCP EF).
+    ///
+    /// *note*: This only applies to IPv4 traffic and is not available on
+    /// Fuchsia, Redox, Solaris, illumos, and Haiku. Windows may ignore the value.
+    ///
+    /// # Arguments
+    /// * `tos` - type-of-service byte (DSCP in the upper six bits)
+    #[cfg(not(any(
+        target_os = "fuchsia",
+        target_os = "redox",
+        target_os = "solaris",
+        target_os = "illumos",
+        target_os = "haiku"
+    )))]
+    pub fn set_tos(&self, tos: u32) -> Result<()> {
+        SockRef::from(&self.socket).set_tos(tos)
+    }
+
+    /// Get the type-of-service byte (i.e. `IP_TOS`) of outgoing packets.
+    #[cfg(not(any(
+        target_os = "fuchsia",
+        target_os = "redox",
+        target_os = "solaris",
+        target_os = "illumos",
+        target_os = "haiku"
+    )))]
+    pub fn tos(&self) -> Result<u32> {
+        SockRef::from(&self.socket).tos()
+    }
+}
+
+#[cfg(test)]
+mod test_netsendudp {
+    use super::*;
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn create_udp_netsend_test_target() {
+        let target = "127.0.0.1:8989";
+        let ns = NetSendUdp::new(&String::from(target));
+
+        assert!(ns.target.is_ipv4());
+        assert_eq!(ns.target.ip(), IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)));
+        assert_eq!(ns.target.port(), 8989);
+    }
+
+    #[test]
+    fn create_udp_netsend_from_hostname() {
+        let ns = NetSendUdp::try_new("localhost:5678").expect("resolving localhost failed");
+        assert!(ns.target.ip().is_loopback());
+        assert_eq!(ns.target.port(), 5678);
+
+        assert!(NetSendUdp::try_new("no-such-host.invalid:5678").is_err());
+    }
+
+    #[test]
+    fn create_udp_netsend_from_ip() {
+        let ns = NetSendUdp::new_ip(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8989)
+            .expect("creating netsend failed");
+        assert_eq!(ns.target.ip(), IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)));
+        assert_eq!(ns.target.port(), 8989);
+
+        let ns = NetSendUdp::new_ip(IpAddr::V6(Ipv6Addr::LOCALHOST), 8989)
+            .expect("creating netsend failed");
+        assert!(ns.target.is_ipv6());
+        assert_eq!(ns.target.ip(), IpAddr::V6(Ipv6Addr::LOCALHOST));
+        assert_eq!(ns.target.port(), 8989);
+    }
+
+    #[test]
+    fn create_udp_netsend_from_addr() {
+        let target = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 8989);
+        let ns = NetSendUdp::from_addr(target).expect("creating netsend failed");
+        assert_eq!(ns.target, target);
+
+        let target = SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 8989);
+        let ns = NetSendUdp::from_addr(target).expect("creating netsend failed");
+        assert_eq!(ns.target, target);
+    }
+
+    #[test]
+    fn send_bang_into_ether() {
+        let msg = PdMessage::Bang;
+        let (ns, nr) = testutil::loopback_pair();
+        let res = ns.send(&msg);
+        match res {
+            Ok(bsend) => assert_eq!(bsend, 6),
+            Err(fail) => panic!("{}", fail),
+        }
+        match nr.receive() {
+            Ok(received) => assert!(received.is_bang()),
+            Err(fail) => panic!("{}", fail),
+        }
+    }
+
+    #[test]
+    fn send_raw_bytes() {
+        let (ns, nr) = testutil::loopback_pair();
+        let res = ns.send_raw(b"custom stuff;\n");
+        match res {
+            Ok(bsend) => assert_eq!(bsend, 14),
+            Err(fail) => panic!("{}", fail),
+        }
+        assert_eq!(b"custom stuff;\n".to_vec(), nr.receive_binary());
+    }
+
+    #[test]
+    fn send_redundant_copies() {
+        let (ns, nr) = testutil::loopback_pair();
+        let res = ns.send_redundant(&PdMessage::Bang, 3, Duration::from_millis(1));
+        match res {
+            Ok(bsend) => assert_eq!(bsend, 3 * 6),
+            Err(fail) => panic!("{}", fail),
+        }
+        for _ in 0..3 {
+            assert_eq!(b"bang;\n".to_vec(), nr.receive_binary());
+        }
+
+        let msg = PdMessage::Symbol(Symbol::escaped(&"x".repeat(MAX_UDP_PAYLOAD)));
+        let err = ns
+            .send_redundant(&msg, 3, Duration::from_millis(1))
+            .unwrap_err();
+        assert_eq!(ErrorKind::InvalidInput, err.kind());
+        match err.get_ref().and_then(|e| e.downcast_ref::<SendError>()) {
+            Some(SendError::TooLarge { .. }) => (),
+            cause => panic!("too large error expected, got {:?}", cause),
+        }
+    }
+
+    #[test]
+    fn send_batch_in_one_datagram() {
+        let (ns, nr) = testutil::loopback_pair();
+        let msgs = [
+            PdMessage::Bang,
+            PdMessage::Float(3.0),
+            PdMessage::Symbol(Symbol::escaped("foo")),
+        ];
+        let res = ns.send_batch(&msgs);
+        match res {
+            Ok(bsend) => assert_eq!(bsend, 6 + 9 + 12),
+            Err(fail) => panic!("{}", fail),
+        }
+
+        let payload = nr.receive_binary();
+        let received = get_messages(payload.as_slice()).expect("parsing batch failed");
+        assert_eq!(3, received.len());
+        assert_eq!("bang;\n", received[0].to_text());
+        assert_eq!("float 3;\n", received[1].to_text());
+        assert_eq!("symbol foo;\n", received[2].to_text());
+    }
+
+    #[test]
+    fn send_oversized_batch_in_several_datagrams() {
+        let (ns, nr) = testutil::loopback_pair();
+        let word = "x".repeat(40000);
+        let msgs = [
+            PdMessage::Symbol(Symbol::new(&word).unwrap()),
+            PdMessage::Symbol(Symbol::new(&word).unwrap()),
+        ];
+        let res = ns.send_batch(&msgs);
+        match res {
+            Ok(bsend) => assert_eq!(bsend, 2 * 40009),
+            Err(fail) => panic!("{}", fail),
+        }
+        assert_eq!(40009, nr.receive_binary().len());
+        assert_eq!(40009, nr.receive_binary().len());
+
+        let msgs = [PdMessage::Symbol(Symbol::escaped(
+            &"x".repeat(MAX_UDP_PAYLOAD),
+        ))];
+        assert!(ns.send_batch(&msgs).is_err());
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn set_type_of_service() {
+        let (ns, nr) = testutil::loopback_pair();
+        ns.set_tos(0xb8).expect("setting type of service failed");
+        assert_eq!(0xb8, ns.tos().expect("getting type of service failed"));
+        ns.send(&PdMessage::Bang).expect("sending failed");
+        assert_eq!(PdMessage::Bang, nr.receive().expect("receiving failed"));
+    }
+
+    #[test]
+    fn reject_oversized_message() {
+        let (ns, _nr) = testutil::loopback_pair();
+        let msg = PdMessage::Symbol(Symbol::escaped(&"x".repeat(MAX_UDP_PAYLOAD)));
+        let len = msg.serialized_len();
+
+        let err = ns.send(&msg).unwrap_err();
+        assert_eq!(ErrorKind::InvalidInput, err.kind());
+        match err.get_ref().and_then(|e| e.downcast_ref::<SendError>()) {
+            Some(SendError::TooLarge { len: l, max }) => {
+                assert_eq!((len, MAX_UDP_PAYLOAD), (*l, *max));
+            }
+            cause => panic!("too large error expected, got {:?}", cause),
+        }
+        match ns.send_checked(&msg) {
+            Err(SendError::TooLarge { .. }) => (),
+            res => panic!("too large error expected, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn send_float_into_ether() {
+        let msg = PdMessage::Float(432.0);
         let target = "127.0.0.1:8989";
         let ns = NetSendUdp::new(&String::from(target));
         let res = ns.send(&msg);
         match res {
             Ok(bsend) => assert_eq!(bsend, 11),
-            Err(fail) => panic!(fail),
+            Err(fail) => panic!("{}", fail),
         }
     }
+
+    #[test]
+    fn send_via_existing_socket() {
+        let nr = NetReceiveUdp::new("127.0.0.1:0");
+        let target = nr.socket.local_addr().unwrap();
+        let socket = UdpSocket::bind("127.0.0.1:0").expect("binding failed");
+        let ns = NetSendUdp::from_socket(socket, target);
+        ns.send(&PdMessage::Bang).expect("sending bang failed");
+        assert_eq!(b"bang;\n".to_vec(), nr.receive_binary());
+    }
+
+    #[test]
+    fn send_without_blocking() {
+        let (ns, nr) = testutil::loopback_pair();
+        match ns.try_send(&PdMessage::Bang) {
+            Ok(bsend) => assert_eq!(bsend, 6),
+            Err(fail) => panic!("{}", fail),
+        }
+        assert_eq!(b"bang;\n".to_vec(), nr.receive_binary());
+        // the mode of the (shared) socket is left alone
+        #[cfg(unix)]
+        {
+            use std::os::unix::io::AsRawFd;
+            let flags = unsafe { libc::fcntl(ns.socket.as_raw_fd(), libc::F_GETFL) };
+            assert_eq!(0, flags & libc::O_NONBLOCK);
+        }
+
+        ns.set_nonblocking(true).expect("switching mode failed");
+        // a full send buffer would be reported as WouldBlock
+        match ns.try_send(&PdMessage::Float(1.0)) {
+            Ok(bsend) => assert_eq!(bsend, 9),
+            Err(ref e) if e.kind() == ErrorKind::WouldBlock => (),
+            Err(fail) => panic!("{}", fail),
+        }
+    }
+
+    #[test]
+    fn send_checked_message() {
+        let (ns, nr) = testutil::loopback_pair();
+        match ns.send_checked(&PdMessage::Float(3.0)) {
+            Ok(()) => (),
+            Err(fail) => panic!("{}", fail),
+        }
+        assert_eq!(b"float 3;\n".to_vec(), nr.receive_binary());
+
+        // more atoms than a receiver accepts by default
+        let msg = PdMessage::List(vec![Atom::Int(1); DEFAULT_MAX_ATOMS + 1]);
+        ns.send_checked(&msg).expect("sending long list failed");
+        assert_eq!(msg.to_bytes(), nr.receive_binary());
+    }
+
+    #[test]
+    fn send_shorthands() {
+        let (ns, nr) = testutil::loopback_pair();
+        assert_eq!(6, ns.bang().expect("sending failed"));
+        assert_eq!(b"bang;\n".to_vec(), nr.receive_binary());
+        assert_eq!(12, ns.float(-2.5).expect("sending failed"));
+        assert_eq!(b"float -2.5;\n".to_vec(), nr.receive_binary());
+        assert_eq!(17, ns.symbol("foo bar").expect("sending failed"));
+        assert_eq!(b"symbol foo\\ bar;\n".to_vec(), nr.receive_binary());
+    }
+
+    #[test]
+    fn send_from_several_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<NetSendUdp>();
+
+        let (ns, nr) = testutil::loopback_pair();
+        let ns = std::sync::Arc::new(ns);
+        let handles: Vec<JoinHandle<()>> = (0..4)
+            .map(|i| {
+                let ns = std::sync::Arc::clone(&ns);
+                thread::spawn(move || {
+                    for j in 0..10 {
+                        ns.float((i * 10 + j) as f32).expect("sending failed");
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("sending thread failed");
+        }
+
+        let mut received: Vec<f32> = (0..40)
+            .map(|_| nr.receive().expect("receiving failed").as_float().unwrap())
+            .collect();
+        received.sort_by(f32::total_cmp);
+        let expected: Vec<f32> = (0..40).map(|i| i as f32).collect();
+        assert_eq!(expected, received);
+    }
+
+    #[test]
+    fn send_checked_errors() {
+        let (ns, _nr) = testutil::loopback_pair();
+        // the unescaped semicolon would split the message
+        let msg = PdMessage::List(vec![Atom::Float(1.0), Atom::Symbol(String::from("a;b"))]);
+        match ns.send_checked(&msg) {
+            Err(SendError::InvalidMessage(ParseError::Malformed)) => (),
+            res => panic!("invalid message error expected, got {:?}", res),
+        }
+        // the trailing backslash escapes the terminator
+        let msg = PdMessage::List(vec![Atom::Float(1.0), Atom::Symbol(String::from("a\\"))]);
+        match ns.send_checked(&msg) {
+            Err(SendError::InvalidMessage(ParseError::MissingTerminator)) => (),
+            res => panic!("invalid message error expected, got {:?}", res),
+        }
+
+        match ns.send_checked(&PdMessage::Float(f32::INFINITY)) {
+            Err(SendError::NonFiniteFloat) => (),
+            res => panic!("non-finite float error expected, got {:?}", res),
+        }
+
+        // an IPv4 socket can not send to an IPv6 target
+        let socket = UdpSocket::bind("127.0.0.1:0").expect("binding failed");
+        let ns = NetSendUdp::from_socket(socket, "[::1]:9".parse().unwrap());
+        match ns.send_checked(&PdMessage::Bang) {
+            Err(SendError::Io(_)) => (),
+            res => panic!("io error expected, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn set_send_buffer_size() {
+        let ns = NetSendUdp::new("127.0.0.1:8989");
+        ns.set_send_buffer_size(65536)
+            .expect("setting buffer size failed");
+        // the operating system may round the size up (e.g. Linux doubles it)
+        assert!(ns.send_buffer_size().unwrap() >= 65536);
+    }
+}
+
+/// Configure and create a `NetSendUdp` with options beyond the target address.
+///
+/// # Examples
+/// ```rust
+/// let netsend = fudi_rs::NetSendUdpBuilder::new("127.0.0.1:5678")
+///     .ttl(4)
+///     .broadcast(false)
+///     .build()
+///     .expect("creating netsend failed");
+/// ```
+#[derive(Debug, Clone)]
+pub struct NetSendUdpBuilder {
+    target: String,
+    bind: Option<String>,
+    ttl: Option<u32>,
+    broadcast: bool,
+    send_buffer_size: Option<usize>,
+    nonblocking: bool,
+}
+
+impl NetSendUdpBuilder {
+    /// Start configuring a sender to the target.
+    ///
+    /// # Arguments
+    /// * `target` - target host (& port) to send messages to
+    pub fn new(target: &str) -> NetSendUdpBuilder {
+        NetSendUdpBuilder {
+            target: target.to_string(),
+            bind: None,
+            ttl: None,
+            broadcast: false,
+            send_buffer_size: None,
+            nonblocking: false,
+        }
+    }
+
+    /// Send from a local address instead of any address and a free port.
+    ///
+    /// # Arguments
+    /// * `addr` - local host (& port) to send from
+    pub fn bind(mut self, addr: &str) -> NetSendUdpBuilder {
+        self.bind = Some(addr.to_string());
+        self
+    }
+
+    /// Set the time-to-live (IPv4) or hop limit (IPv6) of outgoing packets.
+    ///
+    /// # Arguments
+    /// * `ttl` - number of hops a packet may pass
+    pub fn ttl(mut self, ttl: u32) -> NetSendUdpBuilder {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Allow sending to a broadcast address (i.e. `SO_BROADCAST`).
+    ///
+    /// # Arguments
+    /// * `broadcast` - allow broadcasting
+    pub fn broadcast(mut self, broadcast: bool) -> NetSendUdpBuilder {
+        self.broadcast = broadcast;
+        self
+    }
+
+    /// Set the size of the operating system send buffer (see `NetSendUdp::set_send_buffer_size`).
+    ///
+    /// # Arguments
+    /// * `size` - requested buffer size (in bytes)
+    pub fn send_buffer_size(mut self, size: usize) -> NetSendUdpBuilder {
+        self.send_buffer_size = Some(size);
+        self
+    }
+
+    /// Create the sender in non-blocking mode (see `NetSendUdp::set_nonblocking`).
+    ///
+    /// # Arguments
+    /// * `nonblocking` - return `WouldBlock` errors instead of blocking
+    pub fn nonblocking(mut self, nonblocking: bool) -> NetSendUdpBuilder {
+        self.nonblocking = nonblocking;
+        self
+    }
+
+    /// Create the sender with the configured options.
+    pub fn build(&self) -> Result<NetSendUdp> {
+        let target = resolve(&self.target)?;
+        let local = match (&self.bind, target) {
+            (Some(addr), _) => resolve(addr)?,
+            (None, SocketAddr::V4(_)) => SocketAddr::new(IpAddr::from([0, 0, 0, 0]), 0),
+            (None, SocketAddr::V6(_)) => SocketAddr::new(IpAddr::from([0u16; 8]), 0),
+        };
+        let socket = Socket::new(Domain::for_address(local), Type::DGRAM, Some(Protocol::UDP))?;
+        socket.bind(&local.into())?;
+        if let Some(ttl) = self.ttl {
+            match local {
+                SocketAddr::V4(_) => socket.set_ttl(ttl)?,
+                SocketAddr::V6(_) => socket.set_unicast_hops_v6(ttl)?,
+            }
+        }
+        socket.set_broadcast(self.broadcast)?;
+        if let Some(size) = self.send_buffer_size {
+            socket.set_send_buffer_size(size)?;
+        }
+
+        let netsend = NetSendUdp::from_socket(socket.into(), target);
+        netsend.set_nonblocking(self.nonblocking)?;
+        Ok(netsend)
+    }
+}
+
+#[cfg(test)]
+mod test_netsendudpbuilder {
+    use super::*;
+
+    #[test]
+    fn build_with_bind_address_and_ttl() {
+        let (_, nr) = testutil::loopback_pair();
+        let target = nr.local_addr().unwrap().to_string();
+        let ns = NetSendUdpBuilder::new(&target)
+            .bind("127.0.0.1:0")
+            .ttl(7)
+            .build()
+            .expect("creating netsend failed");
+
+        let local = ns.socket.local_addr().unwrap();
+        assert_eq!(IpAddr::from([127, 0, 0, 1]), local.ip());
+        assert_eq!(7, ns.socket.ttl().unwrap());
+        assert!(!ns.socket.broadcast().unwrap());
+
+        ns.bang().expect("sending failed");
+        let (msg, sender) = nr.receive_from().expect("receiving failed");
+        assert_eq!(PdMessage::Bang, msg);
+        assert_eq!(local, sender);
+    }
+
+    #[test]
+    fn build_with_defaults() {
+        let ns = NetSendUdpBuilder::new("127.0.0.1:8989")
+            .broadcast(true)
+            .build()
+            .expect("creating netsend failed");
+        assert!(ns.socket.broadcast().unwrap());
+        assert!(ns.socket.local_addr().unwrap().ip().is_unspecified());
+    }
+}
+
+/// Errors encountered while receiving a message.
+#[derive(Debug)]
+pub enum RecvError {
+    /// The underlying socket reported an error.
+    Io(Error),
+    /// The received payload could not be parsed.
+    Parse(ParseError),
+    /// The payload received from the peer could not be parsed.
+    ParseFrom {
+        addr: SocketAddr,
+        source: ParseError,
+    },
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecvError::Io(err) => write!(f, "receiving failed: {}", err),
+            RecvError::Parse(err) => write!(f, "invalid payload: {}", err),
+            RecvError::ParseFrom { addr, source } => {
+                write!(f, "invalid payload from {}: {}", addr, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RecvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RecvError::Io(err) => Some(err),
+            RecvError::Parse(err) => Some(err),
+            RecvError::ParseFrom { source, .. } => Some(source),
+        }
+    }
+}
+
+// Parse a payload, keeping the address of the sending peer in case of an error.
+fn parse_from(payload: &[u8], addr: SocketAddr) -> std::result::Result<PdMessage, RecvError> {
+    codec::decode(payload).map_err(|source| RecvError::ParseFrom { addr, source })
 }
 
 /// Encapsulate receiving Pure Date messages via FUDI over UDP.
 /// This is the library equivalent of the netreceive-object for UDP.
 ///
+/// The receiver is `Send` and `Sync` and the receive methods take `&self`,
+/// so several threads can receive from one instance wrapped in an `Arc`.
+/// The operating system hands each datagram to exactly one of them.
+///
+/// *note*: `receive_deadline` changes the read timeout of the shared socket
+/// while waiting, which affects the other threads receiving meanwhile.
+///
 /// # references
 /// * [FLOSS manuals: Pure Data - send and receive](http://write.flossmanuals.net/pure-data/send-and-receive/)
 pub struct NetReceiveUdp {
     socket: UdpSocket,
 }
 
-impl NetReceiveUdp {
-    /// Create a new instance and set address to listen on.
+impl NetReceiveUdp {
+    /// Create a new instance and set address to listen on.
+    ///
+    /// # Arguments
+    /// * `addr` - host (& port) to listen for messages
+    pub fn new(addr: &str) -> crate::NetReceiveUdp {
+        NetReceiveUdp::try_new(addr).expect("failed to bind socket to host")
+    }
+
+    /// Create a new instance and set address to listen on, returning
+    /// an error if the address can not be resolved or bound.
+    ///
+    /// # Arguments
+    /// * `addr` - host (& port) to listen for messages
+    pub fn try_new(addr: &str) -> Result<crate::NetReceiveUdp> {
+        Ok(NetReceiveUdp {
+            socket: UdpSocket::bind(addr)?,
+        })
+    }
+
+    /// Create a new instance with `SO_REUSEADDR` set before binding, so the
+    /// address can be bound again right after a previous instance was dropped.
+    ///
+    /// *note*: The semantics of `SO_REUSEADDR` are platform dependent. On Linux
+    /// and macOS several sockets may share a UDP address only if all of them set
+    /// the option. On Windows it allows binding an address already in use by
+    /// another socket, even one of a different process.
+    ///
+    /// # Arguments
+    /// * `addr` - host (& port) to listen for messages
+    pub fn bind_reuse(addr: &str) -> Result<crate::NetReceiveUdp> {
+        let addr = resolve(addr)?;
+        let socket = Socket::new(Domain::for_address(addr), Type::DGRAM, Some(Protocol::UDP))?;
+        socket.set_reuse_address(true)?;
+        socket.bind(&addr.into())?;
+        Ok(NetReceiveUdp {
+            socket: socket.into(),
+        })
+    }
+
+    /// Create a new instance listening on an IPv6 address (e.g. `[::]:5678`)
+    /// which also accepts IPv4 traffic via IPv4-mapped addresses (i.e.
+    /// `IPV6_V6ONLY` is disabled), so a single socket serves both families.
+    ///
+    /// *note*: The default of `IPV6_V6ONLY` is platform dependent (Linux: off unless
+    /// `net.ipv6.bindv6only` is set, Windows and BSDs: on), so it is set explicitly.
+    /// Some platforms (e.g. OpenBSD) do not support dual-stack sockets at all.
+    ///
+    /// # Arguments
+    /// * `addr` - IPv6 host (& port) to listen for messages
+    pub fn bind_dual_stack(addr: &str) -> Result<crate::NetReceiveUdp> {
+        let addr = resolve(addr)?;
+        if !addr.is_ipv6() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "dual-stack requires an IPv6 address",
+            ));
+        }
+        let socket = Socket::new(Domain::IPV6, Type::DGRAM, Some(Protocol::UDP))?;
+        socket.set_only_v6(false)?;
+        socket.bind(&addr.into())?;
+        Ok(NetReceiveUdp {
+            socket: socket.into(),
+        })
+    }
+
+    /// Create a new instance listening on an already resolved address.
+    ///
+    /// # Arguments
+    /// * `addr` - address to listen on for messages
+    pub fn from_addr(addr: SocketAddr) -> Result<crate::NetReceiveUdp> {
+        Ok(NetReceiveUdp {
+            socket: UdpSocket::bind(addr)?,
+        })
+    }
+
+    /// Create a new instance listening on a free port assigned by the
+    /// operating system. Use `local_addr` to find out the port.
+    ///
+    /// # Arguments
+    /// * `host` - IP address to listen on for messages
+    pub fn bind_ephemeral(host: IpAddr) -> Result<crate::NetReceiveUdp> {
+        NetReceiveUdp::from_addr(SocketAddr::new(host, 0))
+    }
+
+    /// Get the address the instance is listening on.
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Create a new instance receiving via an existing socket
+    /// (e.g. one registered with an event loop).
     ///
     /// # Arguments
-    /// * `addr` - host (& port) to listen for messages
-    pub fn new(addr: &str) -> crate::NetReceiveUdp {
-        let laddr = SocketAddr::from_str(addr).expect("failed to parse target address");
-        NetReceiveUdp {
-            socket: UdpSocket::bind(laddr).expect("failed to bind socket to host"),
-        }
+    /// * `socket` - bound socket to receive messages with
+    pub fn from_socket(socket: UdpSocket) -> crate::NetReceiveUdp {
+        NetReceiveUdp { socket }
     }
 
     /// Receive binary data via UDP.
     ///
     /// *note*: This function panics upon errors.
     pub fn receive_binary(&self) -> Vec<u8> {
-        // max 65,507 bytes (65,535 − 8 byte UDP header − 20 byte IP header)
-        let mut buffer = [0; 65535 - 8 - 20];
+        let mut buffer = [0; MAX_UDP_PAYLOAD];
         let recv_result = self.socket.recv_from(&mut buffer);
-        let mut data;
         match recv_result {
-            Ok((amount, _)) => data = Vec::from(&buffer[..amount]),
+            Ok((amount, _)) => Vec::from(&buffer[..amount]),
             Err(e) => panic!("receiving data failed: {:?}", e),
         }
-        data
+    }
+
+    /// Copy the next datagram into the buffer without removing it from the
+    /// queue and return the number of bytes copied. Unlike `receive`,
+    /// calling it again yields the same datagram.
+    ///
+    /// *note*: A datagram larger than the buffer is truncated on Unix,
+    /// but causes an error on Windows.
+    ///
+    /// # Arguments
+    /// * `buf` - buffer for the (beginning of the) datagram
+    pub fn peek(&self, buf: &mut [u8]) -> Result<usize> {
+        let (amount, _) = self.socket.peek_from(buf)?;
+        Ok(amount)
+    }
+
+    /// Set the size of the operating system receive buffer (i.e. `SO_RCVBUF`).
+    /// A larger buffer keeps the kernel from dropping datagrams when Pure Data
+    /// sends bursts of messages faster than they are received.
+    ///
+    /// *note*: The operating system may adjust the size. Linux doubles the
+    /// requested value and enforces limits (see `net.core.rmem_max`).
+    ///
+    /// # Arguments
+    /// * `size` - requested buffer size (in bytes)
+    pub fn set_recv_buffer_size(&self, size: usize) -> Result<()> {
+        SockRef::from(&self.socket).set_recv_buffer_size(size)
+    }
+
+    /// Get the size of the operating system receive buffer (i.e. `SO_RCVBUF`).
+    pub fn recv_buffer_size(&self) -> Result<usize> {
+        SockRef::from(&self.socket).recv_buffer_size()
     }
 
     /// Receive Pure Data messages via UDP.
+    /// Socket errors (e.g. an elapsed read timeout) are returned as they are,
+    /// a payload which can not be parsed is an `InvalidData` error.
     pub fn receive(&self) -> Result<PdMessage> {
-        let payload = self.receive_binary();
-        let res = parser::get_message(payload.as_slice());
+        let mut buffer = [0; MAX_UDP_PAYLOAD];
+        let (amount, _) = self.socket.recv_from(&mut buffer)?;
+        let res = codec::decode(&buffer[..amount]);
         match res {
             Ok(msg) => Ok(msg),
             Err(msg) => {
@@ -248,11 +2150,163 @@ impl NetReceiveUdp {
             }
         }
     }
+
+    /// Receive a Pure Data message, waiting at most until the deadline.
+    /// Returns a `TimedOut` error if no message arrived in time.
+    ///
+    /// # Arguments
+    /// * `deadline` - point in time to stop waiting for a message
+    pub fn receive_deadline(&self, deadline: Instant) -> std::result::Result<PdMessage, RecvError> {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining == Duration::from_secs(0) {
+            return Err(RecvError::Io(Error::new(
+                ErrorKind::TimedOut,
+                "deadline passed",
+            )));
+        }
+
+        let previous = self.socket.read_timeout().map_err(RecvError::Io)?;
+        self.socket
+            .set_read_timeout(Some(remaining))
+            .map_err(RecvError::Io)?;
+        let mut buffer = [0; MAX_UDP_PAYLOAD];
+        let res = self.socket.recv_from(&mut buffer);
+        self.socket
+            .set_read_timeout(previous)
+            .map_err(RecvError::Io)?;
+
+        match res {
+            Ok((amount, addr)) => parse_from(&buffer[..amount], addr),
+            // Unix reports an expired timeout as WouldBlock
+            Err(ref e) if e.kind() == ErrorKind::WouldBlock => Err(RecvError::Io(Error::new(
+                ErrorKind::TimedOut,
+                "deadline passed",
+            ))),
+            Err(e) => Err(RecvError::Io(e)),
+        }
+    }
+
+    /// Switch the socket into (or out of) non-blocking mode.
+    ///
+    /// # Arguments
+    /// * `nonblocking` - return immediately (with `WouldBlock`) if no datagram is available
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        self.socket.set_nonblocking(nonblocking)
+    }
+
+    /// Receive all datagrams which are available right now and return the
+    /// parsed messages (or errors) in the order of arrival.
+    ///
+    /// *note*: The socket has to be in non-blocking mode (see `set_nonblocking`),
+    /// otherwise this call blocks forever.
+    pub fn drain(&self) -> Vec<std::result::Result<PdMessage, RecvError>> {
+        let mut buffer = [0; MAX_UDP_PAYLOAD];
+        let mut messages = vec![];
+        loop {
+            match self.socket.recv_from(&mut buffer) {
+                Ok((amount, addr)) => messages.push(parse_from(&buffer[..amount], addr)),
+                Err(ref e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => {
+                    messages.push(Err(RecvError::Io(e)));
+                    break;
+                }
+            }
+        }
+        messages
+    }
+
+    /// Discard all datagrams which are available right now (e.g. stale ones
+    /// buffered during a stall) and return the number of discarded datagrams.
+    ///
+    /// Only these receives are non-blocking (i.e. `MSG_DONTWAIT`), the mode of
+    /// the socket (see `set_nonblocking`) is left alone.
+    ///
+    /// *note*: On platforms without `MSG_DONTWAIT` (e.g. Windows) the socket has
+    /// to be in non-blocking mode, otherwise this call blocks forever.
+    pub fn flush(&self) -> Result<usize> {
+        // the contents are discarded -> the buffer never has to be initialized
+        let mut buffer = [std::mem::MaybeUninit::<u8>::uninit(); MAX_UDP_PAYLOAD];
+        let socket = SockRef::from(&self.socket);
+        let mut discarded = 0;
+        loop {
+            #[cfg(unix)]
+            let res = socket.recv_from_with_flags(&mut buffer, libc::MSG_DONTWAIT);
+            #[cfg(not(unix))]
+            let res = socket.recv_from(&mut buffer);
+            match res {
+                Ok(_) => discarded += 1,
+                Err(ref e) if e.kind() == ErrorKind::WouldBlock => return Ok(discarded),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Receive a Pure Data message via UDP together with the address of the sender.
+    /// A payload which can not be parsed is reported with the address of the sender.
+    pub fn receive_from(&self) -> std::result::Result<(PdMessage, SocketAddr), RecvError> {
+        let mut buffer = [0; MAX_UDP_PAYLOAD];
+        let (amount, addr) = self.socket.recv_from(&mut buffer).map_err(RecvError::Io)?;
+        let msg = parse_from(&buffer[..amount], addr)?;
+        Ok((msg, addr))
+    }
+
+    /// Receive a Pure Data message via UDP together with the point in time
+    /// it arrived (e.g. to measure jitter). The time is taken right after
+    /// the datagram was read, before parsing it.
+    pub fn receive_timestamped(&self) -> std::result::Result<(PdMessage, Instant), RecvError> {
+        let mut buffer = [0; MAX_UDP_PAYLOAD];
+        let (amount, addr) = self.socket.recv_from(&mut buffer).map_err(RecvError::Io)?;
+        let arrival = Instant::now();
+        let msg = parse_from(&buffer[..amount], addr)?;
+        Ok((msg, arrival))
+    }
+
+    /// Receive Pure Data messages on a background thread and forward
+    /// them over a bounded channel.
+    ///
+    /// Payloads which can not be parsed are skipped. The thread stops
+    /// when forwarding a message fails because the channel receiver
+    /// has been dropped (i.e. with the next message arriving after that),
+    /// or when the socket reports an error (e.g. `WouldBlock` in
+    /// non-blocking mode). Interrupted calls are retried.
+    pub fn spawn(self) -> (JoinHandle<()>, Receiver<PdMessage>) {
+        let (tx, rx) = mpsc::sync_channel(CHANNEL_CAPACITY);
+        let handle = self.on_message(move |msg| tx.send(msg).is_ok());
+        (handle, rx)
+    }
+
+    /// Receive Pure Data messages on a background thread and call
+    /// the callback for each of them.
+    ///
+    /// Payloads which can not be parsed are skipped. The thread stops when
+    /// the callback returns `false` or when the socket reports an error (e.g.
+    /// `WouldBlock` in non-blocking mode). Interrupted calls are retried.
+    ///
+    /// # Arguments
+    /// * `f` - callback handling a received message, returns whether to keep receiving
+    pub fn on_message<F: FnMut(PdMessage) -> bool + Send + 'static>(
+        self,
+        mut f: F,
+    ) -> JoinHandle<()> {
+        thread::spawn(move || loop {
+            match self.receive_from() {
+                Ok((msg, _)) => {
+                    if !f(msg) {
+                        break;
+                    }
+                }
+                Err(RecvError::Io(ref e)) if e.kind() == ErrorKind::Interrupted => (),
+                Err(RecvError::Io(_)) => break,
+                Err(_) => (), // skip unparsable payload
+            }
+        })
+    }
 }
 
 #[cfg(test)]
 mod test_netreceiveudp {
     use super::*;
+    use std::net::Ipv4Addr;
 
     #[test]
     fn create_udp_netreceiveudp_test_target() {
@@ -267,8 +2321,596 @@ mod test_netreceiveudp {
             .expect("could not retrieve socket address");
 
         // test properties
-        assert_eq!(nr_socket.is_ipv4(), true);
+        assert!(nr_socket.is_ipv4());
         assert_eq!(nr_socket.ip(), IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)));
         assert_eq!(nr_socket.port(), 8989);
     }
+
+    #[test]
+    fn bind_ephemeral_port() {
+        let nr =
+            NetReceiveUdp::bind_ephemeral(IpAddr::V4(Ipv4Addr::LOCALHOST)).expect("binding failed");
+        let addr = nr.local_addr().expect("could not retrieve socket address");
+        assert_ne!(0, addr.port());
+
+        let ns = NetSendUdp::from_addr(addr).expect("creating netsend failed");
+        ns.send(&PdMessage::Float(4.0)).expect("sending failed");
+        assert_eq!(
+            PdMessage::Float(4.0),
+            nr.receive().expect("receiving failed")
+        );
+    }
+
+    #[test]
+    fn create_udp_netreceive_from_hostname() {
+        let nr = NetReceiveUdp::try_new("localhost:0").expect("binding localhost failed");
+        let nr_socket = nr
+            .socket
+            .local_addr()
+            .expect("could not retrieve socket address");
+        assert!(nr_socket.ip().is_loopback());
+        assert_ne!(0, nr_socket.port());
+    }
+
+    #[test]
+    fn create_udp_netreceive_from_addr() {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        let nr = NetReceiveUdp::from_addr(addr).expect("creating netreceive failed");
+        let nr_socket = nr
+            .socket
+            .local_addr()
+            .expect("could not retrieve socket address");
+        assert_eq!(nr_socket.ip(), addr.ip());
+        assert_ne!(0, nr_socket.port());
+    }
+
+    #[test]
+    fn peek_before_receiving() {
+        let (ns, nr) = testutil::loopback_pair();
+        ns.send(&PdMessage::Bang).expect("sending bang failed");
+
+        let mut buf = [0; 16];
+        let amount = nr.peek(&mut buf).expect("peeking failed");
+        assert_eq!(b"bang;\n", &buf[..amount]);
+
+        let mut first = [0; 2];
+        assert_eq!(2, nr.peek(&mut first).expect("peeking failed"));
+        assert_eq!(b"ba", &first);
+
+        assert_eq!(buf[..amount].to_vec(), nr.receive_binary());
+    }
+
+    #[test]
+    fn rebind_with_reuse_addr() {
+        let nr = NetReceiveUdp::bind_reuse("127.0.0.1:0").expect("binding failed");
+        let addr = nr.local_addr().expect("could not retrieve socket address");
+        drop(nr);
+        let nr = NetReceiveUdp::bind_reuse(&addr.to_string()).expect("rebinding failed");
+        let nr_socket = nr
+            .socket
+            .local_addr()
+            .expect("could not retrieve socket address");
+        assert_eq!(nr_socket, addr);
+    }
+
+    #[test]
+    fn receive_on_spawned_thread() {
+        let (ns, nr) = testutil::loopback_pair();
+        let (_, rx) = nr.spawn();
+
+        ns.send(&PdMessage::Bang).expect("sending bang failed");
+        ns.send(&PdMessage::Float(7.0))
+            .expect("sending float failed");
+
+        let msg = rx.recv().expect("no message received");
+        assert_eq!("bang;\n", msg.to_text());
+        let msg = rx.recv().expect("no message received");
+        assert_eq!("float 7;\n", msg.to_text());
+    }
+
+    #[test]
+    fn stop_spawned_thread_on_socket_error() {
+        let (_, nr) = testutil::loopback_pair();
+        // no datagram available -> the first receive fails with `WouldBlock`
+        nr.set_nonblocking(true)
+            .expect("setting non-blocking failed");
+        let (handle, rx) = nr.spawn();
+        handle.join().expect("receiving thread panicked");
+        assert!(rx.recv().is_err());
+    }
+
+    #[test]
+    fn receive_from_several_threads() {
+        let (ns, nr) = testutil::loopback_pair();
+        let nr = std::sync::Arc::new(nr);
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..2 {
+            let nr = std::sync::Arc::clone(&nr);
+            let tx = tx.clone();
+            thread::spawn(move || {
+                while let Ok(msg) = nr.receive() {
+                    if tx.send(msg).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        for i in 0..20 {
+            ns.float(i as f32).expect("sending failed");
+        }
+        let mut received: Vec<f32> = (0..20)
+            .map(|_| {
+                let msg = rx
+                    .recv_timeout(Duration::from_secs(5))
+                    .expect("no message received");
+                msg.as_float().unwrap()
+            })
+            .collect();
+        received.sort_by(f32::total_cmp);
+        let expected: Vec<f32> = (0..20).map(|i| i as f32).collect();
+        assert_eq!(expected, received);
+    }
+
+    #[test]
+    fn receive_dual_stack() {
+        assert!(NetReceiveUdp::bind_dual_stack("127.0.0.1:0").is_err());
+        let nr = match NetReceiveUdp::bind_dual_stack("[::]:0") {
+            Ok(nr) => nr,
+            Err(_) => return, // no IPv6 support
+        };
+        let port = nr.local_addr().expect("getting address failed").port();
+
+        let ns = NetSendUdp::new_ip(IpAddr::V4(Ipv4Addr::LOCALHOST), port)
+            .expect("creating netsend failed");
+        ns.bang().expect("sending failed");
+        let (msg, sender) = nr.receive_from().expect("receiving failed");
+        assert_eq!(PdMessage::Bang, msg);
+        match sender {
+            SocketAddr::V6(addr) => {
+                assert_eq!(Some(Ipv4Addr::LOCALHOST), addr.ip().to_ipv4_mapped())
+            }
+            SocketAddr::V4(_) => panic!("IPv4-mapped sender address expected"),
+        }
+    }
+
+    #[test]
+    fn receive_with_callback() {
+        let (ns, nr) = testutil::loopback_pair();
+        let received = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let (tx, rx) = mpsc::channel();
+        let shared = received.clone();
+        nr.on_message(move |msg| {
+            shared.lock().unwrap().push(msg);
+            tx.send(()).unwrap();
+            true
+        });
+
+        ns.send(&PdMessage::Bang).expect("sending bang failed");
+        ns.send(&PdMessage::Float(7.0))
+            .expect("sending float failed");
+        for _ in 0..2 {
+            rx.recv_timeout(Duration::from_secs(5))
+                .expect("no message received");
+        }
+        assert_eq!(
+            vec![PdMessage::Bang, PdMessage::Float(7.0)],
+            *received.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn stop_callback_thread() {
+        let (ns, nr) = testutil::loopback_pair();
+        let mut received = vec![];
+        ns.send(&PdMessage::Bang).expect("sending bang failed");
+        ns.send(&PdMessage::Float(7.0))
+            .expect("sending float failed");
+        let handle = nr.on_message(move |msg| {
+            received.push(msg);
+            // stop after the second message
+            received.len() < 2
+        });
+        handle.join().expect("receiving thread panicked");
+
+        let (_, nr) = testutil::loopback_pair();
+        nr.set_nonblocking(true)
+            .expect("setting non-blocking failed");
+        let handle = nr.on_message(|_| panic!("no message expected"));
+        handle.join().expect("receiving thread panicked");
+    }
+
+    #[test]
+    fn receive_via_existing_socket() {
+        let socket = UdpSocket::bind("127.0.0.1:0").expect("binding failed");
+        let target = socket.local_addr().unwrap();
+        let nr = NetReceiveUdp::from_socket(socket);
+        let ns = NetSendUdp::from_addr(target).expect("creating netsend failed");
+        ns.send(&PdMessage::Float(5.0))
+            .expect("sending float failed");
+        let msg = nr.receive().expect("receiving failed");
+        assert_eq!(Some(5.0), msg.as_float());
+    }
+
+    #[test]
+    fn receive_with_sender_address() {
+        let (ns, nr) = testutil::loopback_pair();
+        let sender = ns.socket.local_addr().unwrap();
+
+        ns.send(&PdMessage::Bang).expect("sending bang failed");
+        let (msg, addr) = nr.receive_from().expect("receiving failed");
+        assert!(msg.is_bang());
+        assert_eq!(sender.port(), addr.port());
+
+        ns.send_raw(b"float;\n").expect("sending payload failed");
+        match nr.receive_from() {
+            Err(RecvError::ParseFrom { addr, source }) => {
+                assert_eq!(sender.port(), addr.port());
+                assert_eq!(ParseError::MissingFloatValue, source);
+            }
+            _ => panic!("parse error expected"),
+        }
+    }
+
+    #[test]
+    fn receive_with_deadline() {
+        let (ns, nr) = testutil::loopback_pair();
+        match nr.receive_deadline(Instant::now()) {
+            Err(RecvError::Io(e)) => assert_eq!(ErrorKind::TimedOut, e.kind()),
+            _ => panic!("timeout expected"),
+        }
+
+        ns.send(&PdMessage::Bang).expect("sending bang failed");
+        let deadline = Instant::now() + Duration::from_secs(1);
+        match nr.receive_deadline(deadline) {
+            Ok(msg) => assert!(msg.is_bang()),
+            Err(fail) => panic!("{}", fail),
+        }
+    }
+
+    #[test]
+    fn drain_available_messages() {
+        let (ns, nr) = testutil::loopback_pair();
+        nr.set_nonblocking(true).expect("switching mode failed");
+        assert!(nr.drain().is_empty());
+
+        ns.send(&PdMessage::Bang).expect("sending bang failed");
+        ns.send(&PdMessage::Float(1.0))
+            .expect("sending float failed");
+        ns.send(&PdMessage::Float(2.0))
+            .expect("sending float failed");
+        // give the datagrams time to arrive
+        thread::sleep(Duration::from_millis(50));
+
+        let messages = nr.drain();
+        assert_eq!(3, messages.len());
+        assert!(messages[0].as_ref().unwrap().is_bang());
+        assert_eq!(Some(2.0), messages[2].as_ref().unwrap().as_float());
+    }
+
+    #[test]
+    fn flush_pending_datagrams() {
+        let (ns, nr) = testutil::loopback_pair();
+        #[cfg(not(unix))]
+        nr.set_nonblocking(true).expect("switching mode failed");
+        assert_eq!(0, nr.flush().expect("flushing failed"));
+
+        for _ in 0..3 {
+            ns.bang().expect("sending bang failed");
+        }
+        // the datagrams may arrive over several calls
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut discarded = 0;
+        while discarded < 3 {
+            assert!(Instant::now() < deadline, "datagrams did not arrive");
+            discarded += nr.flush().expect("flushing failed");
+        }
+        assert_eq!(3, discarded);
+
+        nr.set_nonblocking(true).expect("switching mode failed");
+        match nr.receive_from() {
+            Err(RecvError::Io(e)) => assert_eq!(ErrorKind::WouldBlock, e.kind()),
+            res => panic!("would block error expected, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn receive_with_timestamps() {
+        let (ns, nr) = testutil::loopback_pair();
+        let start = Instant::now();
+        ns.bang().expect("sending bang failed");
+        ns.float(1.0).expect("sending float failed");
+
+        let (msg, first) = nr.receive_timestamped().expect("receiving failed");
+        assert_eq!(PdMessage::Bang, msg);
+        let (msg, second) = nr.receive_timestamped().expect("receiving failed");
+        assert_eq!(PdMessage::Float(1.0), msg);
+        assert!(start <= first && first <= second);
+        assert!(second <= Instant::now());
+    }
+
+    #[test]
+    fn set_recv_buffer_size() {
+        let (_, nr) = testutil::loopback_pair();
+        nr.set_recv_buffer_size(65536)
+            .expect("setting buffer size failed");
+        // the operating system may round the size up (e.g. Linux doubles it)
+        assert!(nr.recv_buffer_size().unwrap() >= 65536);
+    }
+}
+
+/// Configure and create a `NetReceiveUdp` with options beyond the listening address.
+///
+/// # Examples
+/// ```rust
+/// let netreceive = fudi_rs::NetReceiveUdpBuilder::new("127.0.0.1:0")
+///     .reuse_addr(true)
+///     .read_timeout(std::time::Duration::from_millis(500))
+///     .build()
+///     .expect("creating netreceive failed");
+/// ```
+#[derive(Debug, Clone)]
+pub struct NetReceiveUdpBuilder {
+    addr: String,
+    reuse_addr: bool,
+    recv_buffer_size: Option<usize>,
+    read_timeout: Option<Duration>,
+    nonblocking: bool,
+    multicast: Vec<IpAddr>,
+}
+
+impl NetReceiveUdpBuilder {
+    /// Start configuring a receiver listening on the address.
+    ///
+    /// # Arguments
+    /// * `addr` - host (& port) to listen for messages
+    pub fn new(addr: &str) -> NetReceiveUdpBuilder {
+        NetReceiveUdpBuilder {
+            addr: addr.to_string(),
+            reuse_addr: false,
+            recv_buffer_size: None,
+            read_timeout: None,
+            nonblocking: false,
+            multicast: vec![],
+        }
+    }
+
+    /// Set `SO_REUSEADDR` before binding (see `NetReceiveUdp::bind_reuse`).
+    ///
+    /// # Arguments
+    /// * `reuse` - allow binding an address again right after it was released
+    pub fn reuse_addr(mut self, reuse: bool) -> NetReceiveUdpBuilder {
+        self.reuse_addr = reuse;
+        self
+    }
+
+    /// Set the size of the operating system receive buffer (see `NetReceiveUdp::set_recv_buffer_size`).
+    ///
+    /// # Arguments
+    /// * `size` - requested buffer size (in bytes)
+    pub fn recv_buffer_size(mut self, size: usize) -> NetReceiveUdpBuilder {
+        self.recv_buffer_size = Some(size);
+        self
+    }
+
+    /// Give up waiting for a datagram after the timeout, so `receive` returns an error.
+    ///
+    /// # Arguments
+    /// * `timeout` - maximum time to block (must not be zero)
+    pub fn read_timeout(mut self, timeout: Duration) -> NetReceiveUdpBuilder {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Create the receiver in non-blocking mode (see `NetReceiveUdp::set_nonblocking`).
+    ///
+    /// # Arguments
+    /// * `nonblocking` - return `WouldBlock` errors instead of blocking
+    pub fn nonblocking(mut self, nonblocking: bool) -> NetReceiveUdpBuilder {
+        self.nonblocking = nonblocking;
+        self
+    }
+
+    /// Join a multicast group on the default interface. May be called several times.
+    ///
+    /// *note*: The group has to be of the same family as the listening address,
+    /// which usually is the unspecified one (e.g. `0.0.0.0:5678`).
+    ///
+    /// # Arguments
+    /// * `group` - multicast address (e.g. `239.0.0.1`)
+    pub fn join_multicast(mut self, group: IpAddr) -> NetReceiveUdpBuilder {
+        self.multicast.push(group);
+        self
+    }
+
+    /// Create the receiver with the configured options.
+    pub fn build(&self) -> Result<NetReceiveUdp> {
+        let addr = resolve(&self.addr)?;
+        let socket = Socket::new(Domain::for_address(addr), Type::DGRAM, Some(Protocol::UDP))?;
+        socket.set_reuse_address(self.reuse_addr)?;
+        if let Some(size) = self.recv_buffer_size {
+            socket.set_recv_buffer_size(size)?;
+        }
+        socket.bind(&addr.into())?;
+        for group in &self.multicast {
+            match group {
+                IpAddr::V4(group) => socket.join_multicast_v4(group, &Ipv4Addr::UNSPECIFIED)?,
+                IpAddr::V6(group) => socket.join_multicast_v6(group, 0)?,
+            }
+        }
+        socket.set_read_timeout(self.read_timeout)?;
+        socket.set_nonblocking(self.nonblocking)?;
+        Ok(NetReceiveUdp::from_socket(socket.into()))
+    }
+}
+
+#[cfg(test)]
+mod test_netreceiveudpbuilder {
+    use super::*;
+
+    #[test]
+    fn build_with_timeout_and_reuse_addr() {
+        let nr = NetReceiveUdpBuilder::new("127.0.0.1:0")
+            .reuse_addr(true)
+            .read_timeout(Duration::from_millis(50))
+            .build()
+            .expect("binding failed");
+        let addr = nr.local_addr().expect("getting address failed");
+        // the operating system may round the timeout to its clock granularity
+        assert!(nr.socket.read_timeout().unwrap().is_some());
+        let err = nr.receive().unwrap_err();
+        assert!(err.kind() == ErrorKind::WouldBlock || err.kind() == ErrorKind::TimedOut);
+
+        drop(nr);
+        let nr = NetReceiveUdpBuilder::new(&addr.to_string())
+            .reuse_addr(true)
+            .build()
+            .expect("rebinding failed");
+        let ns = NetSendUdp::from_addr(addr).expect("creating netsend failed");
+        ns.bang().expect("sending failed");
+        assert_eq!(PdMessage::Bang, nr.receive().expect("receiving failed"));
+    }
+
+    #[test]
+    fn receive_without_blocking() {
+        let nr = NetReceiveUdpBuilder::new("127.0.0.1:0")
+            .nonblocking(true)
+            .build()
+            .expect("binding failed");
+        let err = nr.receive().unwrap_err();
+        assert_eq!(ErrorKind::WouldBlock, err.kind());
+    }
+
+    #[test]
+    fn join_multicast_group() {
+        let nr = NetReceiveUdpBuilder::new("0.0.0.0:0")
+            .join_multicast(IpAddr::from([239, 255, 0, 1]))
+            .nonblocking(true)
+            .build()
+            .expect("joining group failed");
+        assert!(nr.drain().is_empty());
+    }
+}
+
+/// Replay recorded FUDI messages (e.g. a captured Pd session) to a target.
+///
+/// # Examples
+/// Replay a recorded session with 100 ms between messages.
+/// ```rust,no_run
+/// let netsend = fudi_rs::NetSendUdp::new("127.0.0.1:5678");
+/// let replay = fudi_rs::FudiReplay::new(netsend, Some(std::time::Duration::from_millis(100)));
+/// replay.replay_file("session.fudi").expect("replaying session failed");
+/// ```
+pub struct FudiReplay {
+    netsend: NetSendUdp,
+    delay: Option<Duration>,
+}
+
+impl FudiReplay {
+    /// Create a new instance sending via the given netsend.
+    ///
+    /// # Arguments
+    /// * `netsend` - netsend to send the replayed messages with
+    /// * `delay` - optional pause between two consecutive messages
+    pub fn new(netsend: NetSendUdp, delay: Option<Duration>) -> crate::FudiReplay {
+        FudiReplay { netsend, delay }
+    }
+
+    /// Read semicolon-terminated messages from a source line by line, send
+    /// them to the target and return the number of messages sent.
+    ///
+    /// The source is streamed, so a large recording is never held in memory.
+    /// *note*: Messages are sent as soon as they are read, so the messages before
+    /// a malformed one have been sent already when the error is returned.
+    ///
+    /// # Arguments
+    /// * `source` - source of the recorded FUDI payload
+    pub fn replay<R: Read>(&self, source: R) -> Result<usize> {
+        let mut reader = BufReader::new(source);
+        let mut decoder = Decoder::new();
+        let mut line = vec![];
+        let mut sent = 0;
+        loop {
+            line.clear();
+            if reader.read_until(b'\n', &mut line)? == 0 {
+                break;
+            }
+            decoder.push(&line);
+            while let Some(res) = decoder.next_message() {
+                let msg = res.map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+                if let Some(delay) = self.delay {
+                    if sent > 0 {
+                        thread::sleep(delay);
+                    }
+                }
+                self.netsend.send(&msg)?;
+                sent += 1;
+            }
+        }
+        // unterminated data after the last message is an error (see `get_messages`)
+        if let Err(err) = parser::get_messages(decoder.remainder()) {
+            return Err(Error::new(ErrorKind::InvalidData, err));
+        }
+        Ok(sent)
+    }
+
+    /// Read messages from a file, send them to the target and
+    /// return the number of messages sent.
+    ///
+    /// # Arguments
+    /// * `path` - path of the file containing the recorded FUDI payload
+    pub fn replay_file<P: AsRef<Path>>(&self, path: P) -> Result<usize> {
+        self.replay(File::open(path)?)
+    }
+}
+
+#[cfg(test)]
+mod test_fudireplay {
+    use super::*;
+
+    #[test]
+    fn replay_three_messages() {
+        let (ns, nr) = testutil::loopback_pair();
+        let replay = FudiReplay::new(ns, None);
+
+        let source: &[u8] = b"bang;\nfloat 3;\nsymbol foo;\n";
+        match replay.replay(source) {
+            Ok(sent) => assert_eq!(sent, 3),
+            Err(fail) => panic!("{}", fail),
+        }
+
+        assert_eq!(b"bang;\n".to_vec(), nr.receive_binary());
+        assert_eq!(b"float 3;\n".to_vec(), nr.receive_binary());
+        assert_eq!(b"symbol foo;\n".to_vec(), nr.receive_binary());
+    }
+
+    #[test]
+    fn replay_while_reading() {
+        // fails after the first message was read
+        struct BrokenSource;
+        impl Read for BrokenSource {
+            fn read(&mut self, _: &mut [u8]) -> Result<usize> {
+                Err(Error::other("source broke"))
+            }
+        }
+
+        let (ns, nr) = testutil::loopback_pair();
+        let replay = FudiReplay::new(ns, None);
+        let source = (&b"bang;\nfloat\n3;\n"[..]).chain(BrokenSource);
+        assert!(replay.replay(source).is_err());
+        assert_eq!(b"bang;\n".to_vec(), nr.receive_binary());
+        assert_eq!(b"float 3;\n".to_vec(), nr.receive_binary());
+
+        let (ns, _) = testutil::loopback_pair();
+        let replay = FudiReplay::new(ns, None);
+        let err = replay.replay(&b"bang;\nfloat 3"[..]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, err.kind());
+    }
+
+    #[test]
+    fn replay_missing_file() {
+        let (ns, _) = testutil::loopback_pair();
+        let replay = FudiReplay::new(ns, None);
+        assert!(replay.replay_file("does/not/exist.fudi").is_err());
+    }
 }
@@ -1,32 +1,274 @@
 //! Parse Pure Data Messages using nom.
 
-use crate::{GenericMessage, PdMessage};
-use nom::character::complete::{alphanumeric1, digit1};
-use nom::number::complete::float;
+use crate::{Atom, GenericMessage, PdMessage, Symbol};
+use std::fmt;
+use std::io::BufRead;
 
-extern crate rand;
-use rand::Rng;
+/// Errors encountered while parsing a FUDI payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// The payload could not be parsed at all.
+    Malformed,
+    /// The terminating semicolon is missing.
+    MissingTerminator,
+    /// A float message has no value (e.g. `float;`).
+    MissingFloatValue,
+    /// A symbol message has no value (e.g. `symbol;`).
+    MissingSymbolValue,
+    /// An atom is not valid UTF-8.
+    InvalidUtf8,
+    /// A message exceeds the size limit.
+    TooLong,
+    /// A message does not start with a selector.
+    MissingSelector,
+    /// The selector of a message is not known.
+    UnknownSelector,
+    /// The atoms do not match the selector (e.g. `bang 1;`).
+    InvalidArguments,
+    /// The payload ends with a backslash which escapes nothing.
+    DanglingEscape,
+    /// The payload contains a character not allowed in messages (i.e. NUL).
+    InvalidCharacter,
+    /// A message has more atoms than allowed.
+    TooManyAtoms,
+    /// A payload has more messages than allowed.
+    TooManyMessages,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Malformed => write!(f, "could not parse payload"),
+            ParseError::MissingTerminator => write!(f, "terminating semicolon is missing"),
+            ParseError::MissingFloatValue => write!(f, "float message without value"),
+            ParseError::MissingSymbolValue => write!(f, "symbol message without value"),
+            ParseError::InvalidUtf8 => write!(f, "atom is not valid UTF-8"),
+            ParseError::TooLong => write!(f, "message exceeds size limit"),
+            ParseError::MissingSelector => write!(f, "message does not start with a selector"),
+            ParseError::UnknownSelector => write!(f, "unknown selector"),
+            ParseError::InvalidArguments => write!(f, "atoms do not match selector"),
+            ParseError::DanglingEscape => write!(f, "payload ends with a dangling backslash"),
+            ParseError::InvalidCharacter => write!(f, "payload contains an invalid character"),
+            ParseError::TooManyAtoms => write!(f, "message exceeds atom limit"),
+            ParseError::TooManyMessages => write!(f, "payload exceeds message limit"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Selectors of the message types built into Pure Data.
+const BUILTIN_SELECTORS: [&str; 4] = ["bang", "float", "symbol", "list"];
+
+/// Default upper bound for the number of atoms in a single message.
+pub const DEFAULT_MAX_ATOMS: usize = 4096;
+
+/// Default upper bound for the size of a single message (in bytes).
+pub const DEFAULT_MAX_MESSAGE_BYTES: usize = 65536;
+
+/// Default upper bound for the number of messages in a single payload.
+pub const DEFAULT_MAX_MESSAGES: usize = 4096;
+
+/// Bounds guarding against huge allocations and long parsing caused by
+/// malicious or buggy peers (i.e. denial of service). The defaults are
+/// generous for regular Pure Data traffic.
+///
+/// # Examples
+/// Tighten the limits for a network-facing receiver.
+/// ```rust
+/// let options = fudi_rs::ParserOptions {
+///     limits: fudi_rs::ParserLimits {
+///         max_atoms: 16,
+///         max_message_bytes: 512,
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+/// let res = fudi_rs::get_message_with(b"note 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16;\n", &options);
+/// assert_eq!(Err(fudi_rs::ParseError::TooManyAtoms), res);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParserLimits {
+    /// Maximum number of atoms in a message, exceeding it is
+    /// `ParseError::TooManyAtoms` (default: `DEFAULT_MAX_ATOMS`).
+    pub max_atoms: usize,
+    /// Maximum size of a message including the terminating semicolon, exceeding
+    /// it is `ParseError::TooLong` (default: `DEFAULT_MAX_MESSAGE_BYTES`).
+    pub max_message_bytes: usize,
+    /// Maximum number of messages in a payload (see `get_messages_with`), exceeding
+    /// it is `ParseError::TooManyMessages` (default: `DEFAULT_MAX_MESSAGES`).
+    pub max_messages: usize,
+}
+
+impl Default for ParserLimits {
+    fn default() -> Self {
+        ParserLimits {
+            max_atoms: DEFAULT_MAX_ATOMS,
+            max_message_bytes: DEFAULT_MAX_MESSAGE_BYTES,
+            max_messages: DEFAULT_MAX_MESSAGES,
+        }
+    }
+}
+
+/// Options controlling how parsed atoms are interpreted as messages.
+///
+/// # Examples
+/// Keep empty list messages instead of turning them into bangs.
+/// ```rust
+/// let options = fudi_rs::ParserOptions {
+///     coerce_empty_list: false,
+///     ..Default::default()
+/// };
+/// let msg = fudi_rs::get_message_with(b"list;\n", &options).unwrap();
+/// assert!(matches!(msg, fudi_rs::PdMessage::List(_)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParserOptions {
+    /// Coerce an empty list message (`list;`) into a bang like Pure Data does (default: `true`).
+    pub coerce_empty_list: bool,
+    /// Coerce a list message with a single element into a float (`list 74;`)
+    /// or symbol message (`list foo;`) like Pure Data does (default: `true`).
+    pub coerce_single_lists: bool,
+    /// Match the built-in selectors (`bang`, `float`, `symbol`, `list`) ignoring
+    /// the case (e.g. `Float 3;`). Pure Data is case-sensitive (default: `false`).
+    pub case_insensitive_selectors: bool,
+    /// Bounds of the parsed messages (default: `ParserLimits::default()`).
+    pub limits: ParserLimits,
+    /// Treat double-quoted text (e.g. `"a b"`) as a single atom. This is not
+    /// vanilla FUDI, but used by some tools. The quotes are removed and the
+    /// atom is stored escaped (i.e. `a\ b`). FUDI has no empty atoms, so empty
+    /// quotes (i.e. `""`) are `ParseError::Malformed` (default: `false`).
+    pub allow_quoted_atoms: bool,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            coerce_empty_list: true,
+            coerce_single_lists: true,
+            case_insensitive_selectors: false,
+            limits: ParserLimits::default(),
+            allow_quoted_atoms: false,
+        }
+    }
+}
 
 /// Test character for being considered whitespace in FUDI
-/// (i.e. ASCII 32 (space), 9 (tab), or 10 (newline)).
+/// (i.e. ASCII 32 (space), 9 (tab), 10 (newline), or 13 (carriage return)).
+/// The carriage return is accepted for traffic passing Windows tools.
 fn is_whitespace(c: u8) -> bool {
-    (c == 32) || (c == 9) || (c == 10)
+    (c == 32) || (c == 9) || (c == 10) || (c == 13)
 }
 
 /// Test character for *not* being considered whitespace in FUDI
-/// (i.e. ASCII 32 (space), 9 (tab), or 10 (newline)).
+/// (i.e. ASCII 32 (space), 9 (tab), 10 (newline), or 13 (carriage return)).
 fn is_not_whitespace(c: u8) -> bool {
     !is_whitespace(c)
 }
 
 /// Test for valid character in atom (i.e. not whitespace or semicolon).
+/// Bytes of multibyte UTF-8 sequences are valid atom characters.
 fn valid_atom_character(c: u8) -> bool {
-    is_not_whitespace(c) || c != 59
+    is_not_whitespace(c) && c != 59
+}
+
+/// Test for valid character in atom, which is not the escape character (i.e. backslash).
+fn plain_atom_character(c: u8) -> bool {
+    valid_atom_character(c) && c != 92
+}
+
+/// Find the position of the first semicolon which is not escaped by a backslash.
+pub(crate) fn find_terminator(payload: &[u8]) -> Option<usize> {
+    find_terminator_with(payload, &ParserOptions::default())
+}
+
+/// Find the position of the first semicolon which is not escaped by a backslash
+/// (nor enclosed in double quotes, if quoted atoms are allowed).
+pub(crate) fn find_terminator_with(payload: &[u8], options: &ParserOptions) -> Option<usize> {
+    let mut escaped = false;
+    let mut quoted = false;
+    for (i, &c) in payload.iter().enumerate() {
+        if escaped {
+            escaped = false;
+        } else if c == b'\\' {
+            escaped = true;
+        } else if c == b'"' && options.allow_quoted_atoms {
+            quoted = !quoted;
+        } else if c == b';' && !quoted {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Test whether a string would be serialized as exactly one FUDI atom
+/// (i.e. it is not empty and has no unescaped whitespace or semicolon).
+/// A NUL character is rejected even if escaped, as no message may contain it.
+///
+/// # Examples
+/// ```rust
+/// assert!(fudi_rs::is_valid_atom("foo"));
+/// assert!(fudi_rs::is_valid_atom("foo\\ bar"));
+/// assert!(!fudi_rs::is_valid_atom("foo bar"));
+/// ```
+pub fn is_valid_atom(s: &str) -> bool {
+    let mut escaped = false;
+    for c in s.bytes() {
+        if c == 0 {
+            return false;
+        } else if escaped {
+            escaped = false;
+        } else if c == b'\\' {
+            escaped = true;
+        } else if !valid_atom_character(c) {
+            return false;
+        }
+    }
+    !s.is_empty() && !escaped
+}
+
+/// Escape whitespace, semicolons, and backslashes with a backslash,
+/// so the string is serialized as a single FUDI atom.
+///
+/// # Examples
+/// ```rust
+/// assert_eq!("a\\ b\\;c", fudi_rs::escape_atom("a b;c"));
+/// ```
+pub fn escape_atom(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c == '\\' || c == ';' || (c.is_ascii() && is_whitespace(c as u8)) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Remove the escaping backslashes from a FUDI atom.
+/// A trailing single backslash is kept.
+///
+/// # Examples
+/// ```rust
+/// assert_eq!("a b;c", fudi_rs::unescape_atom("a\\ b\\;c"));
+/// ```
+pub fn unescape_atom(s: &str) -> String {
+    let mut unescaped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            unescaped.push(chars.next().unwrap_or('\\'));
+        } else {
+            unescaped.push(c);
+        }
+    }
+    unescaped
 }
 
 #[cfg(test)]
 mod test_supplements {
     use super::*;
+    use rand::Rng;
 
     #[test]
     fn test_space() {
@@ -43,218 +285,625 @@ mod test_supplements {
         assert!(is_whitespace(b'\n'));
     }
 
+    #[test]
+    fn test_carriage_return() {
+        assert!(is_whitespace(b'\r'));
+    }
+
     #[test]
     fn test_non_whitespace() {
         // generate random ASCII character
         let mut rng = rand::thread_rng();
         let mut t: u8 = rng.gen_range(0..128); // ASCII is 7 bit
 
-        // make sure it is not 9,10, or 32
-        while (t == 9) || (t == 10) || (t == 32) {
-            t = t + 1;
+        // make sure it is not 9, 10, 13, or 32
+        while (t == 9) || (t == 10) || (t == 13) || (t == 32) {
+            t += 1;
         }
 
         // check test function
-        assert_eq!(is_whitespace(t), false);
+        assert!(!is_whitespace(t));
     }
 
     #[test]
     fn valid_atom_chars() {
-        assert!(valid_atom_character(b';'));
+        assert!(valid_atom_character(b'a'));
+        assert!(valid_atom_character(b'$'));
+        assert!(valid_atom_character(0xc3)); // first byte of 'é'
+        assert!(!valid_atom_character(b';'));
+        assert!(!valid_atom_character(b' '));
+    }
+
+    #[test]
+    fn find_unescaped_terminator() {
+        assert_eq!(Some(4), find_terminator(b"bang;\n"));
+        assert_eq!(Some(11), find_terminator(b"symbol a\\;b;\n"));
+        assert_eq!(None, find_terminator(b"symbol a\\;b"));
+        assert_eq!(None, find_terminator(b"float 3"));
+    }
+
+    #[test]
+    fn valid_single_atoms() {
+        assert!(is_valid_atom("foo"));
+        assert!(is_valid_atom("café"));
+        assert!(is_valid_atom("a\\ b"));
+        assert!(!is_valid_atom("foo bar"));
+        assert!(!is_valid_atom("a;b"));
+        assert!(!is_valid_atom("foo\n"));
+        assert!(!is_valid_atom(""));
+        assert!(!is_valid_atom("foo\\"));
+        assert!(!is_valid_atom("foo\0bar"));
+        assert!(!is_valid_atom("foo\\\0"));
+    }
+
+    #[test]
+    fn escaping_atoms() {
+        assert_eq!("a\\ b\\;c", escape_atom("a b;c"));
+        assert_eq!("a b;c", unescape_atom("a\\ b\\;c"));
+        assert_eq!("tab\\\there", escape_atom("tab\there"));
+        assert_eq!("back\\\\slash", escape_atom("back\\slash"));
+        assert_eq!("trailing\\", unescape_atom("trailing\\"));
+
+        for word in ["a b;c", "café au lait", "x\\y", "new\nline"].iter() {
+            let escaped = escape_atom(word);
+            assert!(is_valid_atom(&escaped));
+            assert_eq!(*word, unescape_atom(&escaped));
+        }
     }
 }
 
-named!(parse_message<&[u8], (std::vec::Vec<(((std::option::Option<f32>, std::option::Option<&[u8]>), std::option::Option<&[u8]>), &[u8])>, char)>,
-    many_till!(
-        pair!(
-        parse_atom,
-            take_till!(is_not_whitespace)
-        ),
-        char!(';')
-    )
+// An atom (possibly empty) followed by the whitespace separating it from the next one.
+named!(parse_token<&[u8], &[u8]>,
+    terminated!(parse_atom, take_till!(is_not_whitespace))
 );
 
-// An atom is either an integer, a float, or a string (word)
-named!(parse_atom<&[u8], ((std::option::Option<f32>, std::option::Option<&[u8]>), std::option::Option<&[u8]>)>,
-    pair!(
-        pair!(
-            opt!(float),
-            opt!(digit1)
-    ),
-        opt!(alphanumeric1)
-    )
+// An atom is a sequence of characters other than whitespace or semicolons.
+// A backslash escapes the following character (e.g. `foo\ bar` is one atom).
+named!(parse_atom<&[u8], &[u8]>,
+    recognize!(many0!(alt!(
+        take_while1!(plain_atom_character) |
+        preceded!(char!('\\'), take!(1))
+    )))
 );
 
-// Convert bytes to float.
-fn bytes_to_float(atom: &[u8]) -> Option<f32> {
-    // digits need to be converted to integer
-    if 45 == atom[0] {
-        // negative sign/prefix
-        let word = String::from_utf8(atom[1..].to_vec()).unwrap();
-        let as_int = word.parse::<u32>();
-        if !as_int.is_err() {
-            let val = as_int.unwrap() as f32;
-            return Some(val * -1.0);
-        }
-    } else {
-        let word = String::from_utf8(atom.to_vec()).unwrap();
-        let as_int = word.parse::<u32>();
-        if !as_int.is_err() {
-            let val = as_int.unwrap() as f32;
-            return Some(val * -1.0);
+// A number atom follows the numeric grammar of Pure Data: an optional minus
+// sign, digits with an optional fraction, and an optional exponent (e.g. `-1.5e3`).
+// Other words a float parser would accept (e.g. `nan`, `inf`, or `+3`) are symbols.
+fn parse_number(token: &[u8]) -> Option<f32> {
+    let unsigned = token.strip_prefix(b"-").unwrap_or(token);
+    let mut digits = unsigned.iter().take_while(|c| c.is_ascii_digit()).count();
+    let mut rest = &unsigned[digits..];
+    if let Some(fraction) = rest.strip_prefix(b".") {
+        let fraction_digits = fraction.iter().take_while(|c| c.is_ascii_digit()).count();
+        digits += fraction_digits;
+        rest = &fraction[fraction_digits..];
+    }
+    if digits == 0 {
+        return None;
+    }
+    if let Some(exponent) = rest.strip_prefix(b"e").or_else(|| rest.strip_prefix(b"E")) {
+        let exponent = exponent
+            .strip_prefix(b"-")
+            .or_else(|| exponent.strip_prefix(b"+"))
+            .unwrap_or(exponent);
+        if exponent.is_empty() || !exponent.iter().all(u8::is_ascii_digit) {
+            return None;
         }
+    } else if !rest.is_empty() {
+        return None;
+    }
+    // only ASCII digits, signs, dots, and exponent markers are left
+    std::str::from_utf8(token).ok()?.parse().ok()
+}
+
+// Convert bytes to (UTF-8) string.
+fn bytes_to_string(atom: &[u8]) -> Result<String, ParseError> {
+    match String::from_utf8(atom.to_vec()) {
+        Ok(word) => Ok(word),
+        Err(_) => Err(ParseError::InvalidUtf8),
     }
-    None
 }
 
 /// Retrieve Pure Data message from byte payload.
-/// *note*: This implementation is incomplete and does not handle escaped whitespace inside atoms.
-pub fn get_message(payload: &[u8]) -> Result<PdMessage, &str> {
-    let res = parse_message(payload);
-    if let Ok(parsing_result) = res {
-        let (remainder, chunks) = parsing_result;
-        let (tokens, semicolon) = chunks;
-        if semicolon != ';' {
-            return Err("terminating semicolon is missing");
-        }
-
-        // check for potential bang, float, or list message
-        if 1 == tokens.len() {
-            // extract relevant data (types)
-            let (msg_parts, _) = tokens[0]; // separate potental atoms from whitespace
-            let (number, word) = msg_parts; // split into potential numbers and strings
+/// A message may span several lines, it ends with the semicolon.
+/// Whitespace before the first atom (e.g. `   bang;`) is skipped.
+///
+/// Like Pure Data, an empty list message (`list;`) is coerced into a bang.
+/// Use `get_message_with` to change this behaviour.
+/// Escaped whitespace and semicolons (e.g. `foo\ bar`) are kept inside the atom.
+pub fn get_message(payload: &[u8]) -> Result<PdMessage, ParseError> {
+    get_message_with(payload, &ParserOptions::default())
+}
 
-            // text -> potential bang message
-            if let Some(atom) = word {
-                if atom == "bang".as_bytes() {
-                    return Ok(PdMessage::Bang);
-                }
-                if atom == "list".as_bytes() {
-                    return Ok(PdMessage::Bang);
+/// Get the selector of a message by scanning just its first atom (e.g. to
+/// route a payload before parsing it). A leading number is an implied
+/// float (e.g. `3;`) or list (e.g. `1 2 3;`) selector.
+/// Returns `None` if there is no atom before the terminating semicolon.
+///
+/// *note*: The rest of the message is not validated, so parsing it may still fail.
+///
+/// # Examples
+/// ```rust
+/// assert_eq!(Some("note"), fudi_rs::peek_selector(b"note 60 127;\n"));
+/// assert_eq!(Some("list"), fudi_rs::peek_selector(b"1 2 3;\n"));
+/// ```
+///
+/// # Arguments
+/// * `payload` - FUDI payload of the message
+pub fn peek_selector(payload: &[u8]) -> Option<&str> {
+    let start = payload.iter().position(|&c| is_not_whitespace(c))?;
+    let (rest, token) = parse_atom(&payload[start..]).ok()?;
+    if token.is_empty() {
+        return None;
+    }
+    if parse_number(token).is_some() {
+        let more_atoms = rest
+            .iter()
+            .take_while(|&&c| c != b';')
+            .any(|&c| is_not_whitespace(c));
+        return Some(if more_atoms { "list" } else { "float" });
+    }
+    std::str::from_utf8(token).ok()
+}
+
+/// Retrieve Pure Data message from byte payload using the given options.
+///
+/// # Arguments
+/// * `payload` - FUDI payload of the message
+/// * `options` - options controlling how the message is interpreted
+pub fn get_message_with(payload: &[u8], options: &ParserOptions) -> Result<PdMessage, ParseError> {
+    let limits = &options.limits;
+    check_message_bytes(payload, limits)?;
+    let atoms = if options.allow_quoted_atoms {
+        parse_atoms_limited(&unquote_atoms(payload)?, limits.max_atoms)?
+    } else {
+        parse_atoms_limited(payload, limits.max_atoms)?
+    };
+    atoms_to_message(atoms, options)
+}
+
+// Replace double-quoted text by its escaped form (e.g. `"a b"` becomes `a\ b`)
+// up to the terminating semicolon. Escaped quotes (i.e. `\"`) are kept.
+// Empty quotes are rejected instead of silently dropping the atom.
+fn unquote_atoms(payload: &[u8]) -> Result<Vec<u8>, ParseError> {
+    let mut unquoted = Vec::with_capacity(payload.len());
+    let mut quoted = false;
+    let mut quote_start = 0;
+    let mut bytes = payload.iter();
+    while let Some(&c) = bytes.next() {
+        match c {
+            b'"' if quoted && unquoted.len() == quote_start => return Err(ParseError::Malformed),
+            b'"' => {
+                quoted = !quoted;
+                quote_start = unquoted.len();
+            }
+            b'\\' => {
+                unquoted.push(c);
+                if let Some(&next) = bytes.next() {
+                    unquoted.push(next);
                 }
-                // generic message with only selector
-                return Ok(PdMessage::Generic(GenericMessage {
-                    selector: String::from_utf8(atom.to_vec()).unwrap(),
-                    atoms: vec![],
-                }));
             }
-            // number -> float message
-            let (f, digits) = number; // separate float from integer
-            if let Some(atom) = f {
-                return Ok(PdMessage::Float(atom));
+            b';' if !quoted => {
+                unquoted.push(c);
+                unquoted.extend(bytes);
+                break;
             }
-            if let Some(atom) = digits {
-                let res = bytes_to_float(atom);
-                if let Some(val) = res {
-                    return Ok(PdMessage::Float(val));
+            _ => {
+                if quoted && !plain_atom_character(c) {
+                    unquoted.push(b'\\');
                 }
+                unquoted.push(c);
             }
         }
+    }
+    Ok(unquoted)
+}
 
-        // check for symbol, float, or list messages
-        if 2 == tokens.len() {
-            // extract relevant data (types)
-            let (msg_parts, _) = tokens[0]; // separate potental selector from whitespace
-            let (_, word) = msg_parts; // split into potential numbers and strings
-
-            // text -> selector
-            if let Some(atom) = word {
-                // handle list message with just one element
-                if atom == "list".as_bytes() {
-                    let (msg_parts, _) = tokens[1];
-                    let (number, word) = msg_parts;
+// Interpret the atoms of a message (the first one being the selector).
+pub(crate) fn atoms_to_message(
+    mut atoms: Vec<Atom>,
+    options: &ParserOptions,
+) -> Result<PdMessage, ParseError> {
+    if atoms.is_empty() {
+        return Err(ParseError::Malformed);
+    }
 
-                    // handle number payload as float
-                    let (f, digits) = number; // separate float from integer
-                    if let Some(atom) = f {
-                        return Ok(PdMessage::Float(atom));
-                    }
-                    if let Some(atom) = digits {
-                        let res = bytes_to_float(atom);
-                        if let Some(val) = res {
-                            return Ok(PdMessage::Float(val));
-                        }
-                    }
+    // treat e.g. `Float` like `float`
+    if options.case_insensitive_selectors {
+        if let Atom::Symbol(word) = &mut atoms[0] {
+            let lowercase = word.to_ascii_lowercase();
+            if BUILTIN_SELECTORS.contains(&lowercase.as_str()) {
+                *word = lowercase;
+            }
+        }
+    }
 
-                    // handle text as symbol
-                    if let Some(atom) = word {
-                        return Ok(PdMessage::Symbol(String::from_utf8(atom.to_vec()).unwrap()));
+    // check for potential bang, float, or list message
+    if 1 == atoms.len() {
+        match &atoms[0] {
+            // text -> potential bang message
+            Atom::Symbol(word) => match word.as_str() {
+                "bang" => return Ok(PdMessage::Bang),
+                "list" => {
+                    if options.coerce_empty_list {
+                        return Ok(PdMessage::Bang);
                     }
+                    return Ok(PdMessage::List(vec![]));
                 }
-
-                // handle float message
-                if atom == "float".as_bytes() {
-                    let (msg_parts, _) = tokens[1];
-                    let (number, _) = msg_parts;
-                    // number -> float message
-                    let (f, digits) = number; // separate float from integer
-                    if let Some(atom) = f {
-                        return Ok(PdMessage::Float(atom));
-                    }
-                    if let Some(atom) = digits {
-                        let res = bytes_to_float(atom);
-                        if let Some(val) = res {
-                            return Ok(PdMessage::Float(val));
-                        }
-                    }
+                "float" => return Err(ParseError::MissingFloatValue),
+                "symbol" => return Err(ParseError::MissingSymbolValue),
+                // generic message with only selector
+                _ => {
+                    return Ok(PdMessage::Generic(GenericMessage {
+                        selector: word.clone(),
+                        atoms: vec![],
+                    }))
                 }
+            },
+            // number -> float message
+            Atom::Float(f) => return Ok(PdMessage::Float(*f)),
+            Atom::Int(i) => return Ok(PdMessage::Float(*i as f32)),
+        }
+    }
 
-                // handle symbol message
-                if atom == "symbol".as_bytes() {
-                    let (msg_parts, _) = tokens[1];
-                    let (_, word) = msg_parts;
-                    if let Some(atom) = word {
-                        return Ok(PdMessage::Symbol(String::from_utf8(atom.to_vec()).unwrap()));
-                    }
-
-                    panic!("parsing symbol message not yet implemented");
+    // check for symbol, float, or list messages
+    if 2 == atoms.len() {
+        // text -> selector
+        if let Atom::Symbol(selector) = &atoms[0] {
+            match (selector.as_str(), &atoms[1]) {
+                // keep list message with just one element
+                ("list", atom) if !options.coerce_single_lists => {
+                    return Ok(PdMessage::List(vec![atom.clone()]))
                 }
+                // handle list message with just one element and float message
+                ("list", Atom::Float(f)) | ("float", Atom::Float(f)) => {
+                    return Ok(PdMessage::Float(*f))
+                }
+                ("list", Atom::Int(i)) | ("float", Atom::Int(i)) => {
+                    return Ok(PdMessage::Float(*i as f32))
+                }
+                // handle list message with just one element and symbol message
+                ("list", Atom::Symbol(word)) | ("symbol", Atom::Symbol(word)) => {
+                    return Ok(PdMessage::Symbol(Symbol(word.clone())))
+                }
+                ("symbol", _) => return Err(ParseError::InvalidArguments),
+                _ => (),
             }
         }
+    }
 
-        // message with multiple atoms
-        let mut atoms: Vec<String> = vec![];
-        for tmp in tokens.iter() {
-            let (msg_parts, _) = tmp; // discard whitespace
-            let (_, word) = msg_parts;
-            // handle only text atoms
-            if let Some(atom) = word {
-                atoms.push(String::from_utf8(atom.to_vec()).unwrap());
+    // explicit list-selector -> multi-element list message
+    if let Atom::Symbol(selector) = &atoms[0] {
+        if selector == "list" {
+            atoms.remove(0);
+            return Ok(PdMessage::List(atoms));
+        }
+    }
+
+    // implied list-selector -> multi-element message that starts with a number is a list-message
+    if let Some(Atom::Float(_)) | Some(Atom::Int(_)) = atoms.first() {
+        return Ok(PdMessage::List(atoms));
+    }
+
+    // message with multiple atoms -> valid message, but no pre-defined type
+    // (numbers are kept as atoms of the generic message)
+    Ok(PdMessage::from_atoms(atoms))
+}
+
+// Convert a parsed token into an atom (`None` if there is no atom).
+// A token is a number only if it parses as a number entirely (e.g. `12.5`),
+// otherwise it is a symbol (e.g. `3abc` or `abc3`). Digits with an optional
+// minus sign (e.g. `-3`) fitting into an i64 are an int, other numbers a float.
+pub(crate) fn token_to_atom(token: &[u8]) -> Result<Option<Atom>, ParseError> {
+    if token.is_empty() {
+        return Ok(None);
+    }
+    if let Some(i) = parse_int(token) {
+        return Ok(Some(Atom::Int(i)));
+    }
+    if let Some(f) = parse_number(token) {
+        return Ok(Some(Atom::Float(f)));
+    }
+    Ok(Some(Atom::Symbol(bytes_to_string(token)?)))
+}
+
+// Parse a token consisting of digits with an optional leading minus sign.
+fn parse_int(token: &[u8]) -> Option<i64> {
+    let digits = token.strip_prefix(b"-").unwrap_or(token);
+    if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    std::str::from_utf8(token).ok()?.parse().ok()
+}
+
+// Test whether the payload ends with a backslash which is not escaped itself.
+fn ends_with_escape(payload: &[u8]) -> bool {
+    payload.iter().rev().take_while(|&&c| c == b'\\').count() % 2 == 1
+}
+
+/// Split a byte payload into its typed atoms (up to the terminating semicolon)
+/// without interpreting them as a message.
+///
+/// # Examples
+/// ```rust
+/// use fudi_rs::Atom;
+///
+/// let atoms = fudi_rs::parse_atoms(b"note 60 127;\n").unwrap();
+/// assert_eq!(vec![Atom::Symbol(String::from("note")), Atom::Int(60), Atom::Int(127)], atoms);
+/// ```
+///
+/// *note*: The message is bounded by the default `ParserLimits`,
+/// use `parse_atoms_with` for other limits.
+pub fn parse_atoms(payload: &[u8]) -> Result<Vec<Atom>, ParseError> {
+    parse_atoms_with(payload, &ParserLimits::default())
+}
+
+/// Split a byte payload into its typed atoms, enforcing the size and
+/// atom limits of a message (see `parse_atoms`).
+///
+/// # Arguments
+/// * `payload` - FUDI payload of the message
+/// * `limits` - bounds of the message
+pub fn parse_atoms_with(payload: &[u8], limits: &ParserLimits) -> Result<Vec<Atom>, ParseError> {
+    check_message_bytes(payload, limits)?;
+    parse_atoms_limited(payload, limits.max_atoms)
+}
+
+// Fail if the message (up to and including its terminator) exceeds `max_message_bytes`.
+fn check_message_bytes(payload: &[u8], limits: &ParserLimits) -> Result<(), ParseError> {
+    match find_terminator(payload) {
+        Some(end) if end >= limits.max_message_bytes => Err(ParseError::TooLong),
+        _ => Ok(()),
+    }
+}
+
+// Split a byte payload into its typed atoms, failing if there are more than `max_atoms`.
+fn parse_atoms_limited(payload: &[u8], max_atoms: usize) -> Result<Vec<Atom>, ParseError> {
+    let mut atoms = vec![];
+    for token in tokenize(payload, max_atoms)?.into_iter() {
+        if let Some(atom) = token_to_atom(token)? {
+            atoms.push(atom);
+        }
+    }
+    Ok(atoms)
+}
+
+// Split a byte payload into its (non-empty) tokens up to the terminating semicolon,
+// failing if there are more than `max_atoms`.
+pub(crate) fn tokenize(payload: &[u8], max_atoms: usize) -> Result<Vec<&[u8]>, ParseError> {
+    let end = match find_terminator(payload) {
+        Some(end) => end,
+        None if ends_with_escape(payload) => return Err(ParseError::DanglingEscape),
+        None => return Err(ParseError::MissingTerminator),
+    };
+    if payload[..end].contains(&0) {
+        return Err(ParseError::InvalidCharacter);
+    }
+
+    let mut tokens = vec![];
+    let mut rest = payload;
+    while rest.first() != Some(&b';') {
+        let (remainder, token) = match parse_token(rest) {
+            Ok(parsed) => parsed,
+            Err(_) => return Err(ParseError::Malformed),
+        };
+        // consuming nothing (e.g. at a stray character) would loop forever
+        if remainder.len() == rest.len() {
+            return Err(ParseError::Malformed);
+        }
+        rest = remainder;
+        if !token.is_empty() {
+            // stop before collecting more tokens than allowed
+            if tokens.len() == max_atoms {
+                return Err(ParseError::TooManyAtoms);
             }
+            tokens.push(token);
+        }
+    }
+    Ok(tokens)
+}
+
+/// Retrieve Pure Data message from byte payload, rejecting input
+/// which `get_message` would coerce or interpret on a best-effort basis.
+///
+/// Differences to `get_message`:
+/// * list messages are never coerced (i.e. `list;` is an empty list, not a bang)
+/// * a message has to start with a selector (i.e. `39;` is rejected)
+/// * only the built-in selectors `bang`, `float`, `symbol`, and `list` are accepted
+/// * the atoms have to match the selector (i.e. `bang 1;` is rejected)
+pub fn get_message_strict(payload: &[u8]) -> Result<PdMessage, ParseError> {
+    let mut atoms = parse_atoms(payload)?;
+    if atoms.is_empty() {
+        return Err(ParseError::Malformed);
+    }
+
+    let args = atoms.split_off(1);
+    let selector = match atoms.pop() {
+        Some(Atom::Symbol(word)) => word,
+        _ => return Err(ParseError::MissingSelector),
+    };
+    match (selector.as_str(), args.as_slice()) {
+        ("bang", []) => Ok(PdMessage::Bang),
+        ("float", []) => Err(ParseError::MissingFloatValue),
+        ("symbol", []) => Err(ParseError::MissingSymbolValue),
+        ("float", [Atom::Float(f)]) => Ok(PdMessage::Float(*f)),
+        ("float", [Atom::Int(i)]) => Ok(PdMessage::Float(*i as f32)),
+        ("symbol", [Atom::Symbol(word)]) => Ok(PdMessage::Symbol(Symbol(word.clone()))),
+        ("list", _) => Ok(PdMessage::List(args)),
+        ("bang", _) | ("float", _) | ("symbol", _) => Err(ParseError::InvalidArguments),
+        _ => Err(ParseError::UnknownSelector),
+    }
+}
+
+/// Retrieve all Pure Data messages from a byte payload containing
+/// several semicolon-terminated messages (e.g. a recorded session).
+/// Empty messages (e.g. `;;;`) are skipped.
+/// Whitespace after the last semicolon is ignored, other (i.e. unterminated)
+/// data is an error (`MissingTerminator` or `DanglingEscape`).
+/// *note*: The number of messages is not limited, use `get_messages_with`
+/// for untrusted input.
+pub fn get_messages(payload: &[u8]) -> Result<Vec<PdMessage>, ParseError> {
+    get_messages_limited(payload, &ParserOptions::default(), usize::MAX)
+}
+
+/// Retrieve all Pure Data messages from a byte payload using the given
+/// options, enforcing all of their limits (see `get_messages`).
+///
+/// # Arguments
+/// * `payload` - FUDI payload of the messages
+/// * `options` - options controlling how the messages are interpreted
+pub fn get_messages_with(
+    payload: &[u8],
+    options: &ParserOptions,
+) -> Result<Vec<PdMessage>, ParseError> {
+    get_messages_limited(payload, options, options.limits.max_messages)
+}
+
+// Retrieve all messages from a byte payload, failing if there are more than `max_messages`.
+fn get_messages_limited(
+    payload: &[u8],
+    options: &ParserOptions,
+    max_messages: usize,
+) -> Result<Vec<PdMessage>, ParseError> {
+    let mut messages = vec![];
+    let mut rest = payload;
+    // every iteration consumes at least the semicolon -> the loop terminates
+    while let Some(end) = find_terminator_with(rest, options) {
+        let chunk = &rest[..=end];
+        rest = &rest[end + 1..];
+
+        // skip whitespace (e.g. the newline) left over from the previous message
+        let start = chunk.iter().position(|&c| is_not_whitespace(c)).unwrap();
+        if start == end {
+            continue; // no atoms between semicolons
+        }
+        if messages.len() == max_messages {
+            return Err(ParseError::TooManyMessages);
+        }
+        messages.push(get_message_with(&chunk[start..], options)?);
+    }
+    if rest.iter().any(|&c| is_not_whitespace(c)) {
+        if ends_with_escape(rest) {
+            return Err(ParseError::DanglingEscape);
         }
+        return Err(ParseError::MissingTerminator);
+    }
+    Ok(messages)
+}
+
+/// Read one Pure Data message per line (e.g. from a log file), appending
+/// the terminating semicolon if it is missing. Blank lines are skipped.
+///
+/// *note*: The iteration ends at the first read error.
+///
+/// # Examples
+/// ```rust
+/// let mut messages = fudi_rs::messages_from_lines(&b"bang\nfloat 3;\n"[..]);
+/// assert_eq!(Some(Ok(fudi_rs::PdMessage::Bang)), messages.next());
+/// ```
+///
+/// # Arguments
+/// * `reader` - source of the lines
+pub fn messages_from_lines<R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = Result<PdMessage, ParseError>> {
+    reader
+        .split(b'\n')
+        .map_while(std::io::Result::ok)
+        .filter(|line| line.iter().any(|&c| is_not_whitespace(c)))
+        .map(|mut line| {
+            if find_terminator(&line).is_none() {
+                line.push(b';');
+            }
+            get_message(&line)
+        })
+}
+
+/// Check that a byte payload consists of well-formed FUDI messages (i.e.
+/// terminated by semicolons, valid UTF-8 atoms, no dangling escapes) without
+/// constructing the messages.
+///
+/// # Examples
+/// ```rust
+/// assert!(fudi_rs::validate(b"bang;\nfloat 3;\n").is_ok());
+/// assert!(fudi_rs::validate(b"bang;\nfloat 3").is_err());
+/// ```
+///
+/// *note*: Each message is bounded like in `get_message`, the number of
+/// messages is not limited. Use `validate_with` for other limits.
+pub fn validate(payload: &[u8]) -> Result<(), ParseError> {
+    validate_limited(payload, &ParserLimits::default(), usize::MAX)
+}
+
+/// Check that a byte payload consists of well-formed FUDI messages,
+/// enforcing all of the limits (see `validate`).
+///
+/// # Arguments
+/// * `payload` - FUDI payload of the messages
+/// * `limits` - bounds of the messages
+pub fn validate_with(payload: &[u8], limits: &ParserLimits) -> Result<(), ParseError> {
+    validate_limited(payload, limits, limits.max_messages)
+}
 
-        // valid message, but no pre-defined type
-        return Ok(PdMessage::Generic(GenericMessage {
-            selector: atoms[0].clone(),
-            atoms: atoms[1..].to_vec(),
-        }));
+// Check the messages of a byte payload, failing if there are more than `max_messages`.
+fn validate_limited(
+    payload: &[u8],
+    limits: &ParserLimits,
+    max_messages: usize,
+) -> Result<(), ParseError> {
+    let mut rest = payload;
+    let mut messages = 0;
+    loop {
+        // skip whitespace (e.g. the newline) left over from the previous message
+        let start = rest.iter().position(|&c| is_not_whitespace(c));
+        rest = &rest[start.unwrap_or(rest.len())..];
+        check_message_bytes(rest, limits)?;
+        let tokens = tokenize(rest, limits.max_atoms)?;
+        for token in tokens.iter() {
+            if std::str::from_utf8(token).is_err() {
+                return Err(ParseError::InvalidUtf8);
+            }
+        }
+        if !tokens.is_empty() {
+            if messages == max_messages {
+                return Err(ParseError::TooManyMessages);
+            }
+            messages += 1;
+        }
+        // tokenize succeeded -> there is a terminator
+        rest = &rest[find_terminator(rest).unwrap() + 1..];
+        // only whitespace (e.g. the newline) after the last message
+        if rest.iter().all(|&c| is_whitespace(c)) {
+            return Ok(());
+        }
     }
-    Err("could not parse payload")
 }
 
 #[cfg(test)]
 mod test_parser {
     use super::*;
 
-    /// TODO: negative test
     #[test]
     fn parsing_atom() {
         // positive test
         let res = parse_atom(b"bang;\n");
         if let Ok(parsing_result) = res {
-            let (remainder, tokens) = parsing_result;
+            let (remainder, token) = parsing_result;
             let expected: [u8; 2] = [59, 10];
             assert_eq!(remainder, expected);
-            // unpack all options
-            let (_, text) = tokens;
-            if let Some(sym) = text {
-                let expected = [98, 97, 110, 103];
-                assert_eq!(sym, expected);
-            } else {
-                assert!(false);
-            }
+            let expected = [98, 97, 110, 103];
+            assert_eq!(token, expected);
         } else {
-            assert!(false);
+            panic!("parsing atom failed");
+        }
+
+        // negative test -> no atom before the semicolon
+        match parse_atom(b";\n") {
+            Ok((remainder, token)) => {
+                assert!(token.is_empty());
+                assert_eq!(b";\n", remainder);
+            }
+            Err(_) => panic!("parsing atom failed"),
         }
     }
 
@@ -263,19 +912,13 @@ mod test_parser {
         // capured payload: 0x62 0x61 0x6e 0x67 0x3b 0x0a
         let res = parse_atom(b"bang;\n");
         if let Ok(parsing_result) = res {
-            let (remainder, tokens) = parsing_result;
+            let (remainder, token) = parsing_result;
             let expected: [u8; 2] = [0x3b, 0x0a];
             assert_eq!(remainder, expected);
-            // unpack all options
-            let (_, text) = tokens;
-            if let Some(sym) = text {
-                let expected = [0x62, 0x61, 0x6e, 0x67];
-                assert_eq!(sym, expected);
-            } else {
-                assert!(false);
-            }
+            let expected = [0x62, 0x61, 0x6e, 0x67];
+            assert_eq!(token, expected);
         } else {
-            assert!(false);
+            panic!("parsing atom failed");
         }
 
         // process raw payload
@@ -308,12 +951,6 @@ mod test_parser {
                 Err(msg) => panic!("{}", msg),
             }
 
-            let res = get_message(b"this message continues\nin the following\nline;\n");
-            match res {
-                Ok(message) => assert_eq!("this message continues\nin the following\nline;\n", message.to_text()),
-                Err(msg) => panic!("{}", msg),
-            }
-
             let res = get_message(b"you; can; send; multiple messages; in a line;\n");
             match res {
                 Ok(message) => assert_eq!("you; can; send; multiple messages; in a line;\n", message.to_text()),
@@ -334,6 +971,88 @@ mod test_parser {
         }
     */
 
+    #[test]
+    fn parsing_escaped_atoms() {
+        match get_message(b"symbol foo\\ bar;\n") {
+            Ok(PdMessage::Symbol(word)) => assert_eq!("foo\\ bar", word.as_str()),
+            Ok(_) => panic!("symbol message expected, different type detected"),
+            Err(msg) => panic!("{}", msg),
+        }
+        let messages = get_messages(b"set a\\;b;\nbang;\n").unwrap();
+        assert_eq!(2, messages.len());
+        assert_eq!("set a\\;b;\n", messages[0].to_text());
+    }
+
+    #[test]
+    fn escaped_tabs_stay_in_atom() {
+        // an escaped tab is part of the atom
+        let atoms = parse_atoms(b"a\\\tb;\n").expect("parsing atoms failed");
+        assert_eq!(vec![Atom::Symbol(String::from("a\\\tb"))], atoms);
+        assert_eq!("a\tb", unescape_atom("a\\\tb"));
+        assert_eq!("a\\\tb", escape_atom("a\tb"));
+
+        // an unescaped tab separates atoms
+        let atoms = parse_atoms(b"a\tb;\n").expect("parsing atoms failed");
+        assert_eq!(
+            vec![
+                Atom::Symbol(String::from("a")),
+                Atom::Symbol(String::from("b"))
+            ],
+            atoms
+        );
+
+        // a literal backslash-t is an escaped 't', not a tab
+        let atoms = parse_atoms(b"a\\tb c;\n").expect("parsing atoms failed");
+        assert_eq!(
+            vec![
+                Atom::Symbol(String::from("a\\tb")),
+                Atom::Symbol(String::from("c"))
+            ],
+            atoms
+        );
+
+        let msg = get_message(b"symbol a\\\tb;\n").expect("parsing failed");
+        assert_eq!("symbol a\\\tb;\n", msg.to_text());
+    }
+
+    #[test]
+    fn dollar_arguments_round_trip() {
+        let payload = "set $1 $2;\n";
+        let msg = get_message(payload.as_bytes()).unwrap();
+        assert_eq!(
+            vec![
+                Atom::Symbol(String::from("$1")),
+                Atom::Symbol(String::from("$2"))
+            ],
+            parse_atoms(payload.as_bytes()).unwrap()[1..].to_vec()
+        );
+        assert_eq!(payload, msg.to_text());
+        assert_eq!("set $1;\n", get_message(b"set $1;\n").unwrap().to_text());
+    }
+
+    #[test]
+    fn parsing_multi_line_message() {
+        // spec example: a message continues until the semicolon
+        let res = get_message(b"this message continues\nin the following\nline;\n");
+        match res {
+            Ok(PdMessage::Generic(ref msg)) => {
+                assert_eq!("this", msg.selector);
+                let words = ["message", "continues", "in", "the", "following", "line"];
+                let atoms: Vec<Atom> = words.iter().map(|w| Atom::Symbol(w.to_string())).collect();
+                assert_eq!(atoms, msg.atoms);
+            }
+            Ok(_) => panic!("generic message expected, different type detected"),
+            Err(msg) => panic!("{}", msg),
+        }
+
+        // line breaks between the last atom and the semicolon
+        let res = get_message(b"symbol\nfoo\n;\n");
+        match res {
+            Ok(message) => assert_eq!("symbol foo;\n", message.to_text()),
+            Err(msg) => panic!("{}", msg),
+        }
+    }
+
     #[test]
     fn message_from_bang_only_payload() {
         let res = get_message(b"bang;\n");
@@ -343,6 +1062,30 @@ mod test_parser {
         }
     }
 
+    #[test]
+    fn message_without_trailing_newline() {
+        let res = get_message(b"bang;");
+        match res {
+            Ok(message) => assert!(message.is_bang()),
+            Err(msg) => panic!("{}", msg),
+        }
+
+        let res = get_message(b"float 3;");
+        match res {
+            Ok(message) => assert_eq!(Some(3.0), message.as_float()),
+            Err(msg) => panic!("{}", msg),
+        }
+
+        let res = get_messages(b"bang;symbol foo;");
+        match res {
+            Ok(messages) => {
+                assert_eq!(2, messages.len());
+                assert_eq!(Some("foo"), messages[1].as_symbol());
+            }
+            Err(msg) => panic!("{}", msg),
+        }
+    }
+
     #[test]
     fn message_from_only_alpha_payload() {
         let res = get_message(b"selector;\n");
@@ -401,6 +1144,12 @@ mod test_parser {
         }
     }
 
+    #[test]
+    fn message_from_float_payload_without_value() {
+        let res = get_message(b"float;\n");
+        assert_eq!(ParseError::MissingFloatValue, res.unwrap_err());
+    }
+
     #[test]
     fn message_from_symbol_payload() {
         let res = get_message(b"symbol foo;\n");
@@ -414,16 +1163,28 @@ mod test_parser {
 
         let res = get_message(b"la la;\n");
         match res {
-            Ok(message) => match message {
-                PdMessage::Symbol(_) => {
+            Ok(message) => {
+                if let PdMessage::Symbol(_) = message {
                     panic!("non-symbol message expected, symbol message detected")
                 }
-                _ => (),
-            },
+            }
             Err(msg) => panic!("{}", msg),
         }
     }
 
+    #[test]
+    fn message_from_utf8_symbol_payload() {
+        let res = get_message("symbol café;\n".as_bytes());
+        match res {
+            Ok(PdMessage::Symbol(word)) => assert_eq!("café", word.as_str()),
+            Ok(_) => panic!("symbol message expected, different type detected"),
+            Err(msg) => panic!("{}", msg),
+        }
+
+        let res = get_message(b"symbol caf\xe9;\n"); // Latin-1, not UTF-8
+        assert_eq!(ParseError::InvalidUtf8, res.unwrap_err());
+    }
+
     #[test]
     fn message_from_list_payload() {
         // no data -> conversion to bang-message
@@ -436,6 +1197,23 @@ mod test_parser {
             Err(msg) => panic!("{}", msg),
         }
 
+        // no coercion -> empty list
+        let options = ParserOptions {
+            coerce_empty_list: false,
+            ..Default::default()
+        };
+        let res = get_message_with(b"list;\n", &options);
+        match res {
+            Ok(message) => match message {
+                PdMessage::List(ref atoms) => {
+                    assert!(atoms.is_empty());
+                    assert_eq!("list;\n", message.to_text());
+                }
+                _ => panic!("list message expected, different type detected"),
+            },
+            Err(msg) => panic!("{}", msg),
+        }
+
         // --- one-element lists ---
         // one word -> conversion to symbol-message
         let res = get_message(b"list foo;\n");
@@ -458,6 +1236,633 @@ mod test_parser {
         }
 
         // one pointer -> conversion to pointer
+
         // implied list-selector -> multi-element message that starts with a number is a list-message, too. (Cf. implied selector in float-messages)
+        let res = get_message(b"1 2 3;\n");
+        match res {
+            Ok(PdMessage::List(ref atoms)) => {
+                assert_eq!(&vec![Atom::Int(1), Atom::Int(2), Atom::Int(3)], atoms);
+                assert_eq!("list 1 2 3;\n", res.unwrap().to_text());
+            }
+            Ok(_) => panic!("list message expected, different type detected"),
+            Err(msg) => panic!("{}", msg),
+        }
+        let res = get_message(b"1 foo;\n").expect("parsing list failed");
+        assert_eq!("list 1 foo;\n", res.to_text());
+
+        // explicit list-selector -> multi-element list message
+        let res = get_message(b"list 1 2 3;\n").expect("parsing list failed");
+        assert_eq!(
+            PdMessage::List(vec![Atom::Int(1), Atom::Int(2), Atom::Int(3)]),
+            res
+        );
+        let res = get_message(b"list foo 1;\n").expect("parsing list failed");
+        assert_eq!(
+            PdMessage::List(vec![Atom::Symbol(String::from("foo")), Atom::Int(1)]),
+            res
+        );
+    }
+
+    #[test]
+    fn single_element_list_coercion() {
+        let res = get_message(b"list 74;\n").expect("parsing list failed");
+        assert_eq!(Some(74.0), res.as_float());
+
+        let options = ParserOptions {
+            coerce_single_lists: false,
+            ..Default::default()
+        };
+        let res = get_message_with(b"list 74;\n", &options).expect("parsing list failed");
+        assert_eq!(PdMessage::List(vec![Atom::Int(74)]), res);
+        assert_eq!("list 74;\n", res.to_text());
+        let res = get_message_with(b"list foo;\n", &options).expect("parsing list failed");
+        assert_eq!("list foo;\n", res.to_text());
+    }
+
+    #[test]
+    fn case_insensitive_selectors() {
+        // case-sensitive like Pure Data -> generic message
+        let res = get_message(b"Float 3;\n").expect("parsing failed");
+        assert_eq!("Float", res.selector());
+
+        let options = ParserOptions {
+            case_insensitive_selectors: true,
+            ..Default::default()
+        };
+        let res = get_message_with(b"Float 3;\n", &options).expect("parsing failed");
+        assert_eq!(Some(3.0), res.as_float());
+        let res = get_message_with(b"BANG;\n", &options).expect("parsing failed");
+        assert!(res.is_bang());
+        let res = get_message_with(b"Foo 3;\n", &options).expect("parsing failed");
+        assert_eq!("Foo", res.selector());
+    }
+
+    #[test]
+    fn quoted_atoms() {
+        // quotes are literal in vanilla FUDI
+        let res = parse_atoms(b"symbol \"a b\";\n").expect("parsing atoms failed");
+        assert_eq!(Atom::Symbol(String::from("\"a")), res[1]);
+        assert_eq!(Atom::Symbol(String::from("b\"")), res[2]);
+        let res = get_message(b"symbol \"a\";\n").expect("parsing failed");
+        assert_eq!(Some("\"a\""), res.as_symbol());
+
+        let options = ParserOptions {
+            allow_quoted_atoms: true,
+            ..Default::default()
+        };
+        let res = get_message_with(b"symbol \"a b\";\n", &options).expect("parsing failed");
+        assert_eq!(PdMessage::Symbol(Symbol::escaped("a b")), res);
+        assert_eq!("symbol a\\ b;\n", res.to_text());
+        let res = get_message_with(b"set \"x;y\" \\\"z;\n", &options).expect("parsing failed");
+        assert_eq!("set x\\;y \\\"z;\n", res.to_text());
+        let res = get_message_with(b"symbol \"a b;\n", &options).unwrap_err();
+        assert_eq!(ParseError::MissingTerminator, res);
+        // an empty atom would vanish
+        let res = get_message_with(b"set \"\" x;\n", &options).unwrap_err();
+        assert_eq!(ParseError::Malformed, res);
+    }
+
+    #[test]
+    fn quoted_semicolons_in_several_messages() {
+        let options = ParserOptions {
+            allow_quoted_atoms: true,
+            ..Default::default()
+        };
+        let res = get_messages_with(b"symbol \";\";\nset \"a;b\" c;\nbang;\n", &options)
+            .expect("parsing failed");
+        assert_eq!(3, res.len());
+        assert_eq!(Some("\\;"), res[0].as_symbol());
+        assert_eq!("set a\\;b c;\n", res[1].to_text());
+        assert!(res[2].is_bang());
+        let res = get_messages_with(b"bang;\nsymbol \"a;\n", &options).unwrap_err();
+        assert_eq!(ParseError::MissingTerminator, res);
+    }
+
+    #[test]
+    fn peek_selectors() {
+        assert_eq!(Some("bang"), peek_selector(b"bang;\n"));
+        assert_eq!(Some("float"), peek_selector(b"float 3;\n"));
+        assert_eq!(Some("float"), peek_selector(b"3;\n"));
+        assert_eq!(Some("symbol"), peek_selector(b"symbol foo;\n"));
+        assert_eq!(Some("list"), peek_selector(b"list 1 a;\n"));
+        assert_eq!(Some("list"), peek_selector(b"-1 2;\n"));
+        assert_eq!(Some("note"), peek_selector(b" \tnote 60 127;\n"));
+        assert_eq!(Some("foo\\ bar"), peek_selector(b"foo\\ bar 1;\n"));
+        assert_eq!(None, peek_selector(b";\n"));
+        assert_eq!(None, peek_selector(b"  \n"));
+        assert_eq!(None, peek_selector(b"\xff 1;\n"));
+    }
+
+    #[test]
+    fn messages_from_multi_message_payload() {
+        let res = get_messages(b"bang;\nfloat 3;\nsymbol foo;\n");
+        match res {
+            Ok(messages) => {
+                assert_eq!(3, messages.len());
+                assert_eq!("bang;\n", messages[0].to_text());
+                assert_eq!("float 3;\n", messages[1].to_text());
+                assert_eq!("symbol foo;\n", messages[2].to_text());
+            }
+            Err(msg) => panic!("{}", msg),
+        }
+    }
+
+    #[test]
+    fn data_after_last_message() {
+        assert_eq!(
+            Err(ParseError::MissingTerminator),
+            get_messages(b"bang;\nfloat 3")
+        );
+        assert_eq!(
+            Err(ParseError::DanglingEscape),
+            get_messages(b"bang;\nfoo\\")
+        );
+        assert_eq!(Ok(vec![PdMessage::Bang]), get_messages(b"bang;\n   "));
+        assert_eq!(Ok(vec![PdMessage::Bang]), get_messages(b"bang;"));
+        // a single message ignores the data after its terminator
+        assert_eq!(Ok(PdMessage::Bang), get_message(b"bang;\nfloat 3"));
+    }
+
+    #[test]
+    fn skip_empty_messages() {
+        assert_eq!(Ok(vec![]), get_messages(b";;;\n"));
+        assert_eq!(Ok(vec![]), get_messages(b" ; \n;\t;\n"));
+        assert_eq!(
+            Ok(vec![PdMessage::Bang, PdMessage::Float(1.0)]),
+            get_messages(b";bang;;;float 1;;\n")
+        );
+        // a single message must not be empty
+        assert_eq!(Err(ParseError::Malformed), get_message(b";;;\n"));
+    }
+
+    #[test]
+    fn messages_from_log_lines() {
+        let log = std::io::Cursor::new("bang\nfloat 3;\n\nsymbol foo\r\n");
+        let messages: Result<Vec<PdMessage>, ParseError> = messages_from_lines(log).collect();
+        assert_eq!(
+            vec![
+                PdMessage::Bang,
+                PdMessage::Float(3.0),
+                PdMessage::Symbol(Symbol::escaped("foo"))
+            ],
+            messages.expect("parsing lines failed")
+        );
+
+        let mut messages = messages_from_lines(&b"float;\n"[..]);
+        assert_eq!(Some(Err(ParseError::MissingFloatValue)), messages.next());
+        assert_eq!(None, messages.next());
+    }
+
+    #[test]
+    fn strict_message_from_list_payload() {
+        // lenient parsing coerces the empty list into a bang
+        let res = get_message(b"list;\n");
+        assert_eq!("bang;\n", res.expect("parsing list failed").to_text());
+
+        // strict parsing keeps the empty list
+        let res = get_message_strict(b"list;\n");
+        match res {
+            Ok(PdMessage::List(atoms)) => assert!(atoms.is_empty()),
+            Ok(_) => panic!("list message expected, different type detected"),
+            Err(msg) => panic!("{}", msg),
+        }
+
+        let res = get_message_strict(b"list 74;\n");
+        match res {
+            Ok(message) => assert_eq!("list 74;\n", message.to_text()),
+            Err(msg) => panic!("{}", msg),
+        }
+    }
+
+    #[test]
+    fn strict_message_from_builtin_payloads() {
+        let res = get_message_strict(b"bang;\n");
+        assert_eq!("bang;\n", res.expect("parsing bang failed").to_text());
+        let res = get_message_strict(b"float -5.7;\n");
+        assert_eq!(
+            "float -5.7;\n",
+            res.expect("parsing float failed").to_text()
+        );
+        let res = get_message_strict(b"symbol foo;\n");
+        assert_eq!(
+            "symbol foo;\n",
+            res.expect("parsing symbol failed").to_text()
+        );
+    }
+
+    #[test]
+    fn strict_message_rejections() {
+        let res = get_message_strict(b"selector one two;\n");
+        assert_eq!(ParseError::UnknownSelector, res.unwrap_err());
+        let res = get_message_strict(b"39;\n");
+        assert_eq!(ParseError::MissingSelector, res.unwrap_err());
+        let res = get_message_strict(b"bang 1;\n");
+        assert_eq!(ParseError::InvalidArguments, res.unwrap_err());
+        let res = get_message_strict(b"float foo;\n");
+        assert_eq!(ParseError::InvalidArguments, res.unwrap_err());
+        let res = get_message_strict(b"float;\n");
+        assert_eq!(ParseError::MissingFloatValue, res.unwrap_err());
+        let res = get_message_strict(b"bang\n");
+        assert_eq!(ParseError::MissingTerminator, res.unwrap_err());
+    }
+
+    #[test]
+    fn leading_whitespace() {
+        assert_eq!(Ok(PdMessage::Bang), get_message(b"   bang;\n"));
+        assert_eq!(Ok(PdMessage::Float(3.0)), get_message(b"\t\tfloat 3;\n"));
+        assert_eq!(Ok(PdMessage::Float(3.0)), get_message(b"\r\n 3;\n"));
+        assert_eq!(Ok(PdMessage::Bang), get_message_strict(b" \tbang;\n"));
+        assert_eq!(Some("bang"), peek_selector(b"   bang;\n"));
+    }
+
+    #[test]
+    fn atoms_separated_by_irregular_whitespace() {
+        let expected = vec![
+            Atom::Symbol(String::from("note")),
+            Atom::Int(60),
+            Atom::Int(127),
+        ];
+        let res = parse_atoms(b"note   60\t127;\n");
+        assert_eq!(expected, res.expect("parsing atoms failed"));
+        let res = parse_atoms(b" \t note \t\n 60  127 \t;\n");
+        assert_eq!(expected, res.expect("parsing atoms failed"));
+    }
+
+    #[test]
+    fn path_like_selector() {
+        let res = get_message(b"osc/freq 440;\n").expect("parsing failed");
+        assert_eq!("osc/freq", res.selector());
+        assert_eq!("osc/freq 440;\n", res.to_text());
+        let res = get_message(b"/synth/1/gate on 0.5;\n").expect("parsing failed");
+        assert_eq!("/synth/1/gate", res.selector());
+        assert_eq!("/synth/1/gate on 0.5;\n", res.to_text());
+    }
+
+    #[test]
+    fn messages_with_crlf_line_endings() {
+        let res = get_message(b"bang;\r\n").expect("parsing bang failed");
+        assert!(res.is_bang());
+        let res = get_messages(b"bang;\r\n1\r\n2;\r\n").expect("parsing failed");
+        assert_eq!(2, res.len());
+        assert_eq!("list 1 2;\n", res[1].to_text());
+        assert_eq!(Ok(()), validate(b"bang;\r\n"));
+    }
+
+    #[test]
+    fn atoms_from_payload() {
+        let res = parse_atoms(b"note 60 127;\n");
+        let expected = vec![
+            Atom::Symbol(String::from("note")),
+            Atom::Int(60),
+            Atom::Int(127),
+        ];
+        assert_eq!(expected, res.expect("parsing atoms failed"));
+
+        let res = parse_atoms(b";\n");
+        assert!(res.expect("parsing atoms failed").is_empty());
+
+        let res = parse_atoms(b"note 60 127\n");
+        assert_eq!(ParseError::MissingTerminator, res.unwrap_err());
+    }
+
+    #[test]
+    fn whole_numbers_are_int_atoms() {
+        let res = parse_atoms(b"note 60;\n").expect("parsing atoms failed");
+        assert_eq!(Atom::Int(60), res[1]);
+        let res =
+            parse_atoms(b"60.0 -0 1e2 99999999999999999999;\n").expect("parsing atoms failed");
+        assert_eq!(
+            vec![
+                Atom::Float(60.0),
+                Atom::Int(0),
+                Atom::Float(100.0),
+                Atom::Float(1e20)
+            ],
+            res
+        );
+
+        let res = get_message(b"note 60;\n").expect("parsing failed");
+        assert_eq!("note 60;\n", res.to_text());
+        let res = get_message(b"float 60;\n").expect("parsing failed");
+        assert_eq!(PdMessage::Float(60.0), res);
+    }
+
+    #[test]
+    fn classify_numeric_and_text_atoms() {
+        let res = parse_atoms(b"3abc;\n");
+        assert_eq!(
+            vec![Atom::Symbol(String::from("3abc"))],
+            res.expect("parsing atoms failed")
+        );
+
+        let res = parse_atoms(b"abc3;\n");
+        assert_eq!(
+            vec![Atom::Symbol(String::from("abc3"))],
+            res.expect("parsing atoms failed")
+        );
+
+        let res = parse_atoms(b"12.5;\n");
+        assert_eq!(vec![Atom::Float(12.5)], res.expect("parsing atoms failed"));
+
+        let res = parse_atoms(b"-3 1e3 1e 1.2.3;\n");
+        let expected = vec![
+            Atom::Int(-3),
+            Atom::Float(1000.0),
+            Atom::Symbol(String::from("1e")),
+            Atom::Symbol(String::from("1.2.3")),
+        ];
+        assert_eq!(expected, res.expect("parsing atoms failed"));
+
+        // the whole token is kept as selector
+        let res = get_message(b"3abc;\n");
+        match res {
+            Ok(message) => assert_eq!("3abc;\n", message.to_text()),
+            Err(msg) => panic!("{}", msg),
+        }
+    }
+
+    #[test]
+    fn non_finite_words_are_symbols() {
+        let res = parse_atoms(b"nan NaN inf -inf infinity +3 .5 5. 2E-3;\n");
+        let expected = vec![
+            Atom::Symbol(String::from("nan")),
+            Atom::Symbol(String::from("NaN")),
+            Atom::Symbol(String::from("inf")),
+            Atom::Symbol(String::from("-inf")),
+            Atom::Symbol(String::from("infinity")),
+            Atom::Symbol(String::from("+3")),
+            Atom::Float(0.5),
+            Atom::Float(5.0),
+            Atom::Float(0.002),
+        ];
+        assert_eq!(expected, res.expect("parsing atoms failed"));
+
+        assert_eq!(
+            Ok(PdMessage::Symbol(Symbol(String::from("nan")))),
+            get_message(b"symbol nan;\n")
+        );
+        assert_eq!(
+            Ok(PdMessage::Symbol(Symbol(String::from("inf")))),
+            get_message(b"symbol inf;\n")
+        );
+        let res = get_message(b"set inf;\n").expect("parsing failed");
+        assert_eq!("set inf;\n", res.to_text());
+        match res {
+            PdMessage::Generic(msg) => {
+                assert_eq!(&[Atom::Symbol(String::from("inf"))], msg.atoms())
+            }
+            _ => panic!("generic message expected"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_malformed_input {
+    use super::*;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn terminate_on_pathological_input() {
+        let payloads: Vec<Vec<u8>> = vec![
+            b";".repeat(10000),
+            b"\\".repeat(10000),
+            b"\\;".repeat(10000),
+            b" \t\r\n".repeat(10000),
+            [&b"a"[..], &b"\\ ".repeat(10000), &b";"[..]].concat(),
+            b"\xff;\\".repeat(10000),
+        ];
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for payload in payloads.iter() {
+                let _ = get_message(payload);
+                let _ = get_messages(payload);
+                let _ = validate(payload);
+                let _ = messages_from_lines(payload.as_slice()).count();
+            }
+            tx.send(()).unwrap();
+        });
+        rx.recv_timeout(Duration::from_secs(10))
+            .expect("parsing did not terminate");
+    }
+
+    #[test]
+    fn missing_terminator() {
+        assert_eq!(Err(ParseError::MissingTerminator), parse_atoms(b"bang\n"));
+        assert_eq!(
+            ParseError::MissingTerminator,
+            get_message(b"float 3").unwrap_err()
+        );
+        // the escaped semicolon does not terminate the message
+        assert_eq!(
+            ParseError::MissingTerminator,
+            get_message(b"foo\\;\n").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn dangling_escape() {
+        assert_eq!(
+            ParseError::DanglingEscape,
+            get_message(b"foo\\").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::MissingTerminator,
+            get_message(b"foo\\\\").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn embedded_nul() {
+        assert_eq!(
+            ParseError::InvalidCharacter,
+            get_message(b"symbol foo\0bar;\n").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        assert_eq!(
+            ParseError::InvalidUtf8,
+            get_message(b"symbol \xff\xfe;\n").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::InvalidUtf8,
+            get_message(b"foo \xc3;\n").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn empty_message() {
+        assert_eq!(ParseError::Malformed, get_message(b";\n").unwrap_err());
+        assert_eq!(ParseError::Malformed, get_message(b"  \n;").unwrap_err());
+    }
+
+    #[test]
+    fn too_many_atoms() {
+        let payload = format!("list{};\n", " 1".repeat(DEFAULT_MAX_ATOMS));
+        assert_eq!(
+            ParseError::TooManyAtoms,
+            get_message(payload.as_bytes()).unwrap_err()
+        );
+
+        let options = ParserOptions {
+            limits: ParserLimits {
+                max_atoms: 3,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(get_message_with(b"note 60 127;\n", &options).is_ok());
+        assert_eq!(
+            ParseError::TooManyAtoms,
+            get_message_with(b"note 60 127 1;\n", &options).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn too_long() {
+        let payload = format!("symbol {};\n", "x".repeat(DEFAULT_MAX_MESSAGE_BYTES));
+        assert_eq!(
+            ParseError::TooLong,
+            get_message(payload.as_bytes()).unwrap_err()
+        );
+
+        let options = ParserOptions {
+            limits: ParserLimits {
+                max_message_bytes: 10,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        // the limit includes the semicolon, but not the newline
+        assert!(get_message_with(b"symbol ab;\n", &options).is_ok());
+        assert_eq!(
+            ParseError::TooLong,
+            get_message_with(b"symbol abc;\n", &options).unwrap_err()
+        );
+        assert_eq!(
+            ParseError::TooLong,
+            get_messages_with(b"bang;\nsymbol abc;\n", &options).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn too_many_messages() {
+        let payload = "bang;\n".repeat(DEFAULT_MAX_MESSAGES + 1);
+        assert_eq!(
+            ParseError::TooManyMessages,
+            get_messages_with(payload.as_bytes(), &ParserOptions::default()).unwrap_err()
+        );
+        // not limited for trusted input
+        assert_eq!(
+            DEFAULT_MAX_MESSAGES + 1,
+            get_messages(payload.as_bytes()).unwrap().len()
+        );
+
+        let options = ParserOptions {
+            limits: ParserLimits {
+                max_messages: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(get_messages_with(b"bang;\nbang;\n;\n", &options).is_ok());
+        assert_eq!(
+            ParseError::TooManyMessages,
+            get_messages_with(b"bang;\nbang;\nbang;\n", &options).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn limits_of_helpers() {
+        let limits = ParserLimits {
+            max_atoms: 2,
+            max_message_bytes: 12,
+            max_messages: 2,
+        };
+        assert_eq!(2, parse_atoms_with(b"note 60;\n", &limits).unwrap().len());
+        assert_eq!(
+            ParseError::TooManyAtoms,
+            parse_atoms_with(b"note 60 127;\n", &limits).unwrap_err()
+        );
+        assert_eq!(
+            ParseError::TooLong,
+            parse_atoms_with(b"symbol abcde;\n", &limits).unwrap_err()
+        );
+
+        assert!(validate_with(b"note 60;\n;\nbang;\n", &limits).is_ok());
+        assert_eq!(
+            Err(ParseError::TooManyAtoms),
+            validate_with(b"bang;\nnote 60 127;\n", &limits)
+        );
+        assert_eq!(
+            Err(ParseError::TooLong),
+            validate_with(b"bang;\nsymbol abcde;\n", &limits)
+        );
+        assert_eq!(
+            Err(ParseError::TooManyMessages),
+            validate_with(b"bang;\nbang;\nbang;\n", &limits)
+        );
+        // the number of messages is not limited for trusted input
+        assert!(validate("bang;\n".repeat(DEFAULT_MAX_MESSAGES + 1).as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn stop_tokenizing_at_atom_limit() {
+        let payload = format!("list{};\n", " 1".repeat(100_000));
+        assert_eq!(
+            Err(ParseError::TooManyAtoms),
+            tokenize(payload.as_bytes(), 2)
+        );
+        assert_eq!(3, tokenize(b"list 1 2;\n", 3).unwrap().len());
+    }
+
+    #[test]
+    fn empty_symbol() {
+        assert_eq!(
+            ParseError::MissingSymbolValue,
+            get_message(b"symbol ;\n").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::MissingSymbolValue,
+            get_message_strict(b"symbol;\n").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn symbol_without_symbol_atom() {
+        assert_eq!(
+            ParseError::InvalidArguments,
+            get_message(b"symbol 1;\n").unwrap_err()
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_validate {
+    use super::*;
+
+    #[test]
+    fn valid_payloads() {
+        assert_eq!(Ok(()), validate(b"bang;\n"));
+        assert_eq!(Ok(()), validate(b"bang;\nfloat 3;\nfoo\\ bar 1 2;\n"));
+        assert_eq!(Ok(()), validate(b";\n"));
+        assert_eq!(Ok(()), validate(b";;;\n"));
+        assert_eq!(Ok(()), validate("symbol café;".as_bytes()));
+    }
+
+    #[test]
+    fn malformed_payloads() {
+        assert_eq!(Err(ParseError::MissingTerminator), validate(b""));
+        assert_eq!(Err(ParseError::MissingTerminator), validate(b"\n"));
+        assert_eq!(
+            Err(ParseError::MissingTerminator),
+            validate(b"bang;\nfloat 3")
+        );
+        assert_eq!(Err(ParseError::DanglingEscape), validate(b"bang;\nfoo\\"));
+        assert_eq!(Err(ParseError::InvalidCharacter), validate(b"foo\0;\n"));
+        assert_eq!(Err(ParseError::InvalidUtf8), validate(b"symbol \xff;\n"));
     }
 }
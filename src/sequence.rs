@@ -0,0 +1,184 @@
+//! Monitor lossy links by numbering messages (without making delivery reliable).
+//!
+//! The sender prepends a sequence number as leading atom (e.g. `float 3;`
+//! becomes `7 float 3;`), the receiver strips it and tracks gaps.
+//! *note*: Pure Data stores the numbers as floats, so they are exact up to 2^24 in a patch.
+
+use crate::{parser, Atom, GenericMessage, ParserOptions, PdMessage};
+
+/// Prepend a sequence number to a message (e.g. `float 3;` becomes `7 float 3;`).
+///
+/// # Arguments
+/// * `seq` - sequence number of the message
+/// * `msg` - message to number
+pub fn add_sequence_number(seq: u32, msg: &PdMessage) -> PdMessage {
+    let (selector, mut atoms) = match msg {
+        PdMessage::Bang => ("bang", vec![]),
+        PdMessage::Float(f) => ("float", vec![Atom::Float(*f)]),
+        PdMessage::Symbol(word) => ("symbol", vec![Atom::Symbol(word.as_str().to_string())]),
+        PdMessage::List(items) => ("list", items.clone()),
+        PdMessage::Generic(generic) => (generic.selector(), generic.atoms().to_vec()),
+    };
+    atoms.insert(0, Atom::Symbol(selector.to_string()));
+    PdMessage::Generic(GenericMessage::new(&seq.to_string(), atoms))
+}
+
+/// Split a numbered message into its sequence number and the original message.
+/// Returns `None` if the message does not start with a sequence number.
+///
+/// # Arguments
+/// * `msg` - received message (e.g. `7 float 3;`)
+pub fn strip_sequence_number(msg: &PdMessage) -> Option<(u32, PdMessage)> {
+    let atoms = match msg {
+        PdMessage::List(atoms) => atoms,
+        _ => return None,
+    };
+    let seq = match atoms.first() {
+        Some(Atom::Int(i)) if *i >= 0 && *i <= i64::from(u32::MAX) => *i as u32,
+        _ => return None,
+    };
+    let original = parser::atoms_to_message(atoms[1..].to_vec(), &ParserOptions::default()).ok()?;
+    Some((seq, original))
+}
+
+/// Result of tracking the sequence number of a received message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceEvent {
+    /// The message arrived in order.
+    InOrder,
+    /// The messages between the expected and the received one are missing.
+    Gap { expected: u32, received: u32 },
+    /// The message arrived after a later one (i.e. reordered or duplicated).
+    Late { expected: u32, received: u32 },
+}
+
+/// Track the sequence numbers of received messages and report gaps.
+///
+/// # Examples
+/// ```rust
+/// use fudi_rs::{SequenceEvent, SequenceTracker};
+///
+/// let mut tracker = SequenceTracker::new();
+/// assert_eq!(SequenceEvent::InOrder, tracker.observe(1));
+/// assert_eq!(SequenceEvent::Gap { expected: 2, received: 3 }, tracker.observe(3));
+/// ```
+#[derive(Debug, Default)]
+pub struct SequenceTracker {
+    expected: Option<u32>,
+    missing: u64,
+}
+
+impl SequenceTracker {
+    /// Create a new instance accepting any first sequence number.
+    pub fn new() -> SequenceTracker {
+        SequenceTracker::default()
+    }
+
+    /// Track the sequence number of a received message.
+    ///
+    /// # Arguments
+    /// * `seq` - sequence number of the received message
+    pub fn observe(&mut self, seq: u32) -> SequenceEvent {
+        let expected = match self.expected {
+            Some(expected) => expected,
+            None => seq,
+        };
+        if seq < expected {
+            return SequenceEvent::Late {
+                expected,
+                received: seq,
+            };
+        }
+        self.expected = Some(seq.wrapping_add(1));
+        if seq == expected {
+            return SequenceEvent::InOrder;
+        }
+        self.missing += u64::from(seq - expected);
+        SequenceEvent::Gap {
+            expected,
+            received: seq,
+        }
+    }
+
+    /// Get the total number of messages reported missing so far.
+    pub fn missing(&self) -> u64 {
+        self.missing
+    }
+}
+
+#[cfg(test)]
+mod test_sequence {
+    use super::*;
+    use crate::testutil;
+
+    #[test]
+    fn number_and_strip_messages() {
+        let msg = add_sequence_number(7, &PdMessage::Float(3.0));
+        assert_eq!("7 float 3;\n", msg.to_text());
+
+        let received = parser::get_message(&msg.to_bytes()).unwrap();
+        let (seq, original) = strip_sequence_number(&received).expect("no sequence number");
+        assert_eq!(7, seq);
+        assert_eq!(PdMessage::Float(3.0), original);
+
+        assert!(strip_sequence_number(&PdMessage::Bang).is_none());
+    }
+
+    #[test]
+    fn number_messages_beyond_parser_limits() {
+        let items = vec![Atom::Int(1); 5000];
+        let msg = add_sequence_number(3, &PdMessage::List(items.clone()));
+        match msg {
+            PdMessage::Generic(generic) => {
+                assert_eq!("3", generic.selector());
+                assert_eq!(5001, generic.atoms().len());
+                assert_eq!(&items[..], &generic.atoms()[1..]);
+            }
+            _ => panic!("generic message expected"),
+        }
+
+        let msg = PdMessage::from_atoms(vec![
+            Atom::Symbol(String::from("set")),
+            Atom::Symbol(String::from("a\0b")),
+        ]);
+        let numbered = add_sequence_number(4, &msg);
+        assert_eq!("4 set a\0b;\n", numbered.to_text());
+    }
+
+    #[test]
+    fn report_dropped_message() {
+        let (ns, nr) = testutil::loopback_pair();
+        for seq in [1, 3].iter() {
+            // message 2 is lost on the way
+            ns.send(&add_sequence_number(*seq, &PdMessage::Bang))
+                .expect("sending failed");
+        }
+
+        let mut tracker = SequenceTracker::new();
+        let mut events = vec![];
+        for _ in 0..2 {
+            let msg = nr.receive().expect("receiving failed");
+            let (seq, original) = strip_sequence_number(&msg).expect("no sequence number");
+            assert!(original.is_bang());
+            events.push(tracker.observe(seq));
+        }
+        assert_eq!(
+            vec![
+                SequenceEvent::InOrder,
+                SequenceEvent::Gap {
+                    expected: 2,
+                    received: 3
+                }
+            ],
+            events
+        );
+        assert_eq!(1, tracker.missing());
+        assert_eq!(
+            SequenceEvent::Late {
+                expected: 4,
+                received: 2
+            },
+            tracker.observe(2)
+        );
+    }
+}
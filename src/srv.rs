@@ -0,0 +1,151 @@
+//! Discover the Pure Data host via DNS SRV records (e.g. `_fudi._udp.example.com`).
+//!
+//! The crate does not ship a DNS client. Implement `SrvResolver` with the
+//! resolver of your choice to plug it in.
+
+use crate::{NetSendTcp, NetSendUdp};
+use std::io::{Error, ErrorKind, Result};
+
+/// A DNS SRV record pointing to a service endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SrvRecord {
+    pub priority: u16,
+    pub weight: u16,
+    pub port: u16,
+    pub target: String,
+}
+
+/// Look up the SRV records of a service.
+pub trait SrvResolver {
+    /// Get all SRV records of a service name (e.g. `_fudi._udp.example.com`).
+    ///
+    /// # Arguments
+    /// * `name` - service name to look up
+    fn lookup_srv(&self, name: &str) -> Result<Vec<SrvRecord>>;
+}
+
+/// Resolve a service name to a `host:port` target.
+///
+/// The record with the lowest priority is selected, ties are broken
+/// by the highest weight.
+///
+/// *note*: The selection is deterministic, there is no weighted
+/// load balancing across records.
+///
+/// # Arguments
+/// * `resolver` - resolver to look up SRV records with
+/// * `name` - service name to look up
+pub fn resolve_srv<R: SrvResolver + ?Sized>(resolver: &R, name: &str) -> Result<String> {
+    let records = resolver.lookup_srv(name)?;
+    let best = records
+        .iter()
+        .min_by_key(|record| (record.priority, std::cmp::Reverse(record.weight)));
+    match best {
+        // a target of "." means the service is explicitly not available
+        Some(record) if record.target != "." => Ok(format!(
+            "{}:{}",
+            record.target.trim_end_matches('.'),
+            record.port
+        )),
+        _ => Err(Error::new(
+            ErrorKind::NotFound,
+            "service not available via SRV",
+        )),
+    }
+}
+
+impl NetSendUdp {
+    /// Create a new instance sending to the target of a SRV record.
+    ///
+    /// # Arguments
+    /// * `resolver` - resolver to look up SRV records with
+    /// * `name` - service name to look up
+    pub fn from_srv<R: SrvResolver + ?Sized>(resolver: &R, name: &str) -> Result<NetSendUdp> {
+        NetSendUdp::try_new(&resolve_srv(resolver, name)?)
+    }
+}
+
+impl NetSendTcp {
+    /// Create a new instance and connect to the target of a SRV record.
+    ///
+    /// # Arguments
+    /// * `resolver` - resolver to look up SRV records with
+    /// * `name` - service name to look up
+    pub fn from_srv<R: SrvResolver + ?Sized>(resolver: &R, name: &str) -> Result<NetSendTcp> {
+        NetSendTcp::try_new(&resolve_srv(resolver, name)?)
+    }
+}
+
+#[cfg(test)]
+mod test_srv {
+    use super::*;
+    use crate::{NetReceiveUdp, PdMessage};
+    use std::io::Read;
+    use std::net::TcpListener;
+
+    struct MockResolver(Vec<SrvRecord>);
+
+    impl SrvResolver for MockResolver {
+        fn lookup_srv(&self, name: &str) -> Result<Vec<SrvRecord>> {
+            assert_eq!("_fudi._udp.example.com", name);
+            Ok(self.0.clone())
+        }
+    }
+
+    fn record(priority: u16, weight: u16, port: u16, target: &str) -> SrvRecord {
+        SrvRecord {
+            priority,
+            weight,
+            port,
+            target: target.to_string(),
+        }
+    }
+
+    #[test]
+    fn select_record() {
+        let resolver = MockResolver(vec![
+            record(20, 100, 1, "backup.example.com."),
+            record(10, 5, 2, "small.example.com."),
+            record(10, 50, 3, "pd.example.com."),
+        ]);
+        assert_eq!(
+            "pd.example.com:3",
+            resolve_srv(&resolver, "_fudi._udp.example.com").unwrap()
+        );
+
+        let resolver = MockResolver(vec![]);
+        let err = resolve_srv(&resolver, "_fudi._udp.example.com").unwrap_err();
+        assert_eq!(ErrorKind::NotFound, err.kind());
+        let resolver = MockResolver(vec![record(0, 0, 0, ".")]);
+        let err = resolve_srv(&resolver, "_fudi._udp.example.com").unwrap_err();
+        assert_eq!(ErrorKind::NotFound, err.kind());
+    }
+
+    #[test]
+    fn send_udp_to_srv_target() {
+        let nr = NetReceiveUdp::new("127.0.0.1:0");
+        let port = nr.socket.local_addr().unwrap().port();
+        let resolver = MockResolver(vec![record(0, 0, port, "127.0.0.1.")]);
+
+        let ns = NetSendUdp::from_srv(&resolver, "_fudi._udp.example.com")
+            .expect("creating netsend failed");
+        ns.send(&PdMessage::Bang).expect("sending failed");
+        assert_eq!(PdMessage::Bang, nr.receive().expect("receiving failed"));
+    }
+
+    #[test]
+    fn send_tcp_to_srv_target() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("binding failed");
+        let port = listener.local_addr().unwrap().port();
+        let resolver = MockResolver(vec![record(0, 0, port, "127.0.0.1")]);
+
+        let ns =
+            NetSendTcp::from_srv(&resolver, "_fudi._udp.example.com").expect("connecting failed");
+        let (mut peer, _) = listener.accept().expect("accepting failed");
+        ns.send(&PdMessage::Bang).expect("sending failed");
+        drop(ns);
+        let mut received = vec![];
+        peer.read_to_end(&mut received).expect("reading failed");
+        assert_eq!(b"bang;\n".to_vec(), received);
+    }
+}
@@ -0,0 +1,420 @@
+//! Send and receive Pure Data messages via FUDI over TCP.
+
+use crate::{parser, resolve, ParseError, ParserLimits, ParserOptions, PdMessage};
+use socket2::{Domain, Protocol, Socket, Type};
+use std::io::{Error, ErrorKind, Read, Result, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::Mutex;
+
+/// Encapsulate sending Pure Data messages via FUDI over TCP.
+/// This is the library equivalent of the netsend-object for TCP.
+///
+/// The connection is shut down when the instance is dropped.
+pub struct NetSendTcp {
+    target: String,
+    stream: Mutex<TcpStream>,
+    reconnect: bool,
+}
+
+impl NetSendTcp {
+    /// Create a new instance and connect to the target.
+    ///
+    /// # Arguments
+    /// * `target` - target host (& port) to send messages to
+    pub fn new(target: &str) -> crate::NetSendTcp {
+        NetSendTcp::try_new(target).expect("failed to connect to target")
+    }
+
+    /// Create a new instance and connect to the target, returning
+    /// an error if the connection can not be established.
+    ///
+    /// # Arguments
+    /// * `target` - target host (& port) to send messages to
+    pub fn try_new(target: &str) -> Result<crate::NetSendTcp> {
+        NetSendTcp::connect(target, false)
+    }
+
+    /// Create a new instance and connect to the target, optionally
+    /// reconnecting if the connection breaks (e.g. Pure Data reloads the patch).
+    ///
+    /// With `reconnect` enabled, a send failing because of a broken connection
+    /// reconnects to the target and retries the send once.
+    ///
+    /// # Arguments
+    /// * `target` - target host (& port) to send messages to
+    /// * `reconnect` - re-establish a broken connection when sending
+    pub fn connect(target: &str, reconnect: bool) -> Result<crate::NetSendTcp> {
+        Ok(NetSendTcp {
+            target: target.to_string(),
+            stream: Mutex::new(TcpStream::connect(target)?),
+            reconnect,
+        })
+    }
+
+    /// Create a new instance connecting from a fixed local address (e.g. for
+    /// a firewall only accepting a known source port).
+    ///
+    /// *note*: This is rarely needed, usually the operating system picks a
+    /// free source port. `SO_REUSEADDR` is set, so the port can be reused while
+    /// a previous connection lingers in `TIME_WAIT`, but only one connection
+    /// to the same target can use the port at a time. A broken connection
+    /// is not re-established.
+    ///
+    /// # Arguments
+    /// * `local` - local host (& port) to connect from
+    /// * `target` - target host (& port) to send messages to
+    pub fn with_bind(local: &str, target: &str) -> Result<crate::NetSendTcp> {
+        let local = resolve(local)?;
+        let socket = Socket::new(
+            Domain::for_address(local),
+            Type::STREAM,
+            Some(Protocol::TCP),
+        )?;
+        socket.set_reuse_address(true)?;
+        socket.bind(&local.into())?;
+        socket.connect(&resolve(target)?.into())?;
+        Ok(NetSendTcp {
+            target: target.to_string(),
+            stream: Mutex::new(socket.into()),
+            reconnect: false,
+        })
+    }
+
+    /// Get the local address the connection originates from.
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        self.stream.lock().unwrap().local_addr()
+    }
+
+    /// Test whether a broken connection is re-established when sending.
+    pub fn reconnects(&self) -> bool {
+        self.reconnect
+    }
+
+    /// Send a message to the target and return the number of bytes sent.
+    ///
+    /// # Arguments
+    /// * `msg` - message to send to the target
+    pub fn send(&self, msg: &PdMessage) -> Result<usize> {
+        let payload = msg.to_text();
+        let mut stream = self.stream.lock().unwrap();
+        match stream.write_all(payload.as_bytes()) {
+            Err(ref e) if self.reconnect && is_broken_connection(e) => {
+                *stream = TcpStream::connect(self.target.as_str())?;
+                stream.write_all(payload.as_bytes())?;
+            }
+            res => res?,
+        }
+        Ok(payload.len())
+    }
+
+    /// Shut down the connection, so the peer sees a clean disconnect.
+    pub fn shutdown(&self) -> Result<()> {
+        self.stream.lock().unwrap().shutdown(Shutdown::Both)
+    }
+}
+
+impl Drop for NetSendTcp {
+    fn drop(&mut self) {
+        // the peer may have closed the connection already
+        let _ = self.shutdown();
+    }
+}
+
+// Test whether an error means the connection has been closed by the peer.
+fn is_broken_connection(err: &Error) -> bool {
+    matches!(
+        err.kind(),
+        ErrorKind::BrokenPipe
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected
+    )
+}
+
+#[cfg(test)]
+mod test_netsendtcp {
+    use super::*;
+
+    #[test]
+    fn send_bang_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("binding failed");
+        let ns = NetSendTcp::new(&listener.local_addr().unwrap().to_string());
+        let (mut peer, _) = listener.accept().expect("accepting failed");
+
+        match ns.send(&PdMessage::Bang) {
+            Ok(bsend) => assert_eq!(bsend, 6),
+            Err(fail) => panic!("{}", fail),
+        }
+
+        // dropping the netsend shuts the connection down -> peer reads EOF
+        drop(ns);
+        let mut received = vec![];
+        peer.read_to_end(&mut received).expect("reading failed");
+        assert_eq!(b"bang;\n".to_vec(), received);
+    }
+
+    #[test]
+    fn connect_from_fixed_source() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("binding failed");
+        let target = listener.local_addr().unwrap().to_string();
+        // find a free source port
+        let local = TcpListener::bind("127.0.0.1:0")
+            .and_then(|free| free.local_addr())
+            .expect("finding free port failed");
+        let ns = NetSendTcp::with_bind(&local.to_string(), &target).expect("connecting failed");
+        let (_peer, source) = listener.accept().expect("accepting failed");
+
+        assert_eq!(local, source);
+        assert_eq!(source, ns.local_addr().expect("getting address failed"));
+        assert!(!ns.reconnects());
+    }
+
+    #[test]
+    fn reconnect_after_dropped_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("binding failed");
+        let addr = listener.local_addr().unwrap();
+        let ns = NetSendTcp::connect(&addr.to_string(), true).expect("connecting failed");
+        assert!(ns.reconnects());
+        let (peer, _) = listener.accept().expect("accepting failed");
+
+        // the patch is reloaded -> connection and listener are gone for a moment
+        drop(peer);
+        drop(listener);
+        let listener = TcpListener::bind(addr).expect("binding failed");
+        listener.set_nonblocking(true).unwrap();
+
+        // the first send after the peer left may still succeed (and get lost)
+        let mut peer = None;
+        for _ in 0..10 {
+            ns.send(&PdMessage::Bang).expect("sending failed");
+            if let Ok((stream, _)) = listener.accept() {
+                peer = Some(stream);
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        let mut peer = peer.expect("sender did not reconnect");
+        peer.set_nonblocking(false).unwrap();
+
+        drop(ns);
+        let mut received = vec![];
+        peer.read_to_end(&mut received).expect("reading failed");
+        assert_eq!(b"bang;\n".to_vec(), received);
+    }
+}
+
+/// Encapsulate receiving Pure Data messages via FUDI over TCP.
+/// This is the library equivalent of the netreceive-object for TCP.
+///
+/// The receiver listens for a connection (e.g. from a netsend-object)
+/// and reads semicolon-terminated messages from it. The connection is
+/// shut down when the instance is dropped.
+pub struct NetReceiveTcp {
+    listener: TcpListener,
+    stream: Option<TcpStream>,
+    buffer: Vec<u8>,
+    options: ParserOptions,
+}
+
+impl NetReceiveTcp {
+    /// Create a new instance and set address to listen on.
+    ///
+    /// # Arguments
+    /// * `addr` - host (& port) to listen for connections
+    pub fn new(addr: &str) -> crate::NetReceiveTcp {
+        NetReceiveTcp::try_new(addr).expect("failed to bind socket to host")
+    }
+
+    /// Create a new instance and set address to listen on, returning
+    /// an error if the socket can not be bound.
+    ///
+    /// # Arguments
+    /// * `addr` - host (& port) to listen for connections
+    pub fn try_new(addr: &str) -> Result<crate::NetReceiveTcp> {
+        Ok(NetReceiveTcp {
+            listener: TcpListener::bind(addr)?,
+            stream: None,
+            buffer: vec![],
+            options: ParserOptions::default(),
+        })
+    }
+
+    /// Limit the number of bytes buffered while waiting for the
+    /// terminating semicolon of a message.
+    ///
+    /// # Arguments
+    /// * `max` - maximum size of a message (in bytes)
+    pub fn set_max_message_bytes(&mut self, max: usize) {
+        self.options.limits.max_message_bytes = max;
+    }
+
+    /// Set the bounds of received messages (e.g. the number of atoms).
+    ///
+    /// # Arguments
+    /// * `limits` - bounds of the received messages
+    pub fn set_limits(&mut self, limits: ParserLimits) {
+        self.options.limits = limits;
+    }
+
+    /// Receive Pure Data messages via TCP.
+    ///
+    /// Waits for a connection if there is none. A message exceeding the
+    /// size limit is reported as `ParseError::TooLong` (wrapped in an
+    /// `InvalidData` error). After a malformed or overlong message the
+    /// connection is shut down, as the following bytes can not be framed reliably.
+    pub fn receive(&mut self) -> Result<PdMessage> {
+        loop {
+            let frame = self.read_frame()?;
+            match parser::get_messages_with(frame.as_slice(), &self.options) {
+                Ok(mut messages) => {
+                    if let Some(msg) = messages.pop() {
+                        return Ok(msg);
+                    }
+                }
+                Err(msg) => {
+                    let _ = self.shutdown();
+                    return Err(Error::new(ErrorKind::InvalidData, msg));
+                }
+            }
+        }
+    }
+
+    /// Shut down the current connection (if any), so the peer sees a clean
+    /// disconnect. The next call to `receive` waits for a new connection.
+    pub fn shutdown(&mut self) -> Result<()> {
+        self.buffer.clear();
+        match self.stream.take() {
+            Some(stream) => stream.shutdown(Shutdown::Both),
+            None => Ok(()),
+        }
+    }
+
+    // Read bytes up to (and including) the next unescaped semicolon.
+    fn read_frame(&mut self) -> Result<Vec<u8>> {
+        if self.stream.is_none() {
+            let (stream, _) = self.listener.accept()?;
+            self.stream = Some(stream);
+        }
+
+        let max_message_bytes = self.options.limits.max_message_bytes;
+        let mut chunk = [0; 1024];
+        loop {
+            match parser::find_terminator_with(&self.buffer, &self.options) {
+                Some(end) if end < max_message_bytes => {
+                    return Ok(self.buffer.drain(..=end).collect());
+                }
+                None if self.buffer.len() <= max_message_bytes => (),
+                _ => {
+                    let _ = self.shutdown();
+                    return Err(Error::new(ErrorKind::InvalidData, ParseError::TooLong));
+                }
+            }
+
+            // read at most one byte more than allowed to detect overlong messages
+            let wanted = (max_message_bytes + 1 - self.buffer.len()).min(chunk.len());
+            let received = self.stream.as_mut().unwrap().read(&mut chunk[..wanted])?;
+            if received == 0 {
+                let _ = self.shutdown();
+                return Err(Error::new(
+                    ErrorKind::UnexpectedEof,
+                    "connection closed before terminating semicolon",
+                ));
+            }
+            self.buffer.extend_from_slice(&chunk[..received]);
+        }
+    }
+}
+
+impl Drop for NetReceiveTcp {
+    fn drop(&mut self) {
+        // the peer may have closed the connection already
+        let _ = self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod test_netreceivetcp {
+    use super::*;
+
+    #[test]
+    fn receive_messages_over_tcp() {
+        let mut nr = NetReceiveTcp::new("127.0.0.1:0");
+        let addr = nr.listener.local_addr().unwrap();
+        let mut client = TcpStream::connect(addr).expect("connecting failed");
+        client
+            .write_all(b"bang;\nfloat 3;\n")
+            .expect("writing failed");
+
+        assert_eq!("bang;\n", nr.receive().expect("receiving failed").to_text());
+        assert_eq!(
+            "float 3;\n",
+            nr.receive().expect("receiving failed").to_text()
+        );
+    }
+
+    #[test]
+    fn receive_escaped_semicolon() {
+        let mut nr = NetReceiveTcp::try_new("127.0.0.1:0").expect("binding failed");
+        let addr = nr.listener.local_addr().unwrap();
+        let mut client = TcpStream::connect(addr).expect("connecting failed");
+        client
+            .write_all(b"symbol a\\;b;\nbang;\n")
+            .expect("writing failed");
+
+        assert_eq!(
+            Some("a\\;b"),
+            nr.receive().expect("receiving failed").as_symbol()
+        );
+        assert!(nr.receive().expect("receiving failed").is_bang());
+    }
+
+    #[test]
+    fn reject_overlong_message() {
+        let mut nr = NetReceiveTcp::new("127.0.0.1:0");
+        let addr = nr.listener.local_addr().unwrap();
+        nr.set_max_message_bytes(16);
+        let mut client = TcpStream::connect(addr).expect("connecting failed");
+        client.write_all(&[b'a'; 32]).expect("writing failed");
+
+        let err = nr.receive().unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, err.kind());
+        let cause = err.get_ref().and_then(|e| e.downcast_ref::<ParseError>());
+        assert_eq!(Some(&ParseError::TooLong), cause);
+    }
+
+    #[test]
+    fn reject_too_many_atoms() {
+        let mut nr = NetReceiveTcp::new("127.0.0.1:0");
+        let addr = nr.listener.local_addr().unwrap();
+        nr.set_limits(ParserLimits {
+            max_atoms: 2,
+            ..Default::default()
+        });
+        let mut client = TcpStream::connect(addr).expect("connecting failed");
+        client.write_all(b"note 60 127;\n").expect("writing failed");
+
+        let err = nr.receive().unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, err.kind());
+        let cause = err.get_ref().and_then(|e| e.downcast_ref::<ParseError>());
+        assert_eq!(Some(&ParseError::TooManyAtoms), cause);
+
+        // the connection is closed after the malformed message
+        let mut received = vec![];
+        client.read_to_end(&mut received).expect("reading failed");
+        assert!(received.is_empty());
+    }
+
+    #[test]
+    fn shutdown_on_drop() {
+        let mut nr = NetReceiveTcp::new("127.0.0.1:0");
+        let addr = nr.listener.local_addr().unwrap();
+        let mut client = TcpStream::connect(addr).expect("connecting failed");
+        client.write_all(b"bang;\n").expect("writing failed");
+        assert_eq!("bang;\n", nr.receive().expect("receiving failed").to_text());
+
+        drop(nr);
+        let mut received = vec![];
+        client.read_to_end(&mut received).expect("reading failed");
+        assert!(received.is_empty());
+    }
+}
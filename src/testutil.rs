@@ -0,0 +1,14 @@
+//! Helpers shared by the tests.
+
+use crate::{NetReceiveUdp, NetSendUdp};
+use std::net::{IpAddr, Ipv4Addr};
+
+/// Create a netreceive listening on an ephemeral loopback port
+/// and a netsend targeting it.
+pub fn loopback_pair() -> (NetSendUdp, NetReceiveUdp) {
+    let nr =
+        NetReceiveUdp::bind_ephemeral(IpAddr::V4(Ipv4Addr::LOCALHOST)).expect("binding failed");
+    let addr = nr.local_addr().expect("could not retrieve socket address");
+    let ns = NetSendUdp::from_addr(addr).expect("creating netsend failed");
+    (ns, nr)
+}
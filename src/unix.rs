@@ -0,0 +1,120 @@
+//! Send and receive Pure Data messages via FUDI over Unix domain datagram sockets.
+
+use crate::{codec, PdMessage, MAX_UDP_PAYLOAD};
+use std::io::{Error, ErrorKind, Result};
+use std::os::unix::net::UnixDatagram;
+use std::path::{Path, PathBuf};
+
+/// Encapsulate sending Pure Data messages via FUDI over a Unix domain datagram socket.
+/// This avoids the IP stack when talking to a Pure Data instance (with a
+/// suitable external) on the same machine.
+pub struct NetSendUnix {
+    target: PathBuf,
+    socket: UnixDatagram,
+}
+
+impl NetSendUnix {
+    /// Create a new instance and set the socket path to send messages to.
+    ///
+    /// # Arguments
+    /// * `target` - path of the socket to send messages to
+    pub fn new<P: AsRef<Path>>(target: P) -> Result<crate::NetSendUnix> {
+        Ok(NetSendUnix {
+            target: target.as_ref().to_path_buf(),
+            socket: UnixDatagram::unbound()?,
+        })
+    }
+
+    /// Send a message to the target and return the number of bytes sent.
+    ///
+    /// # Arguments
+    /// * `msg` - message to send to the target
+    pub fn send(&self, msg: &PdMessage) -> Result<usize> {
+        self.socket
+            .send_to(&codec::encode(msg), self.target.as_path())
+    }
+}
+
+#[cfg(test)]
+mod test_netsendunix {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn send_bang_over_unix_socket() {
+        let path = std::env::temp_dir().join(format!("fudi-rs-{}-send.sock", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let peer = UnixDatagram::bind(&path).expect("binding failed");
+
+        let ns = NetSendUnix::new(&path).expect("creating netsend failed");
+        match ns.send(&PdMessage::Bang) {
+            Ok(bsend) => assert_eq!(bsend, 6),
+            Err(fail) => panic!("{}", fail),
+        }
+        let mut buffer = [0; 16];
+        let amount = peer.recv(&mut buffer).expect("receiving failed");
+        assert_eq!(b"bang;\n", &buffer[..amount]);
+        fs::remove_file(&path).unwrap();
+    }
+}
+
+/// Encapsulate receiving Pure Data messages via FUDI over a Unix domain datagram socket.
+///
+/// The socket file is removed when the instance is dropped.
+pub struct NetReceiveUnix {
+    path: PathBuf,
+    socket: UnixDatagram,
+}
+
+impl NetReceiveUnix {
+    /// Create a new instance and bind it to the socket path.
+    ///
+    /// # Arguments
+    /// * `path` - path of the socket to receive messages on
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<crate::NetReceiveUnix> {
+        Ok(NetReceiveUnix {
+            path: path.as_ref().to_path_buf(),
+            socket: UnixDatagram::bind(path)?,
+        })
+    }
+
+    /// Receive Pure Data messages via the Unix domain socket.
+    pub fn receive(&self) -> Result<PdMessage> {
+        let mut buffer = vec![0; MAX_UDP_PAYLOAD];
+        let amount = self.socket.recv(&mut buffer)?;
+        match codec::decode(&buffer[..amount]) {
+            Ok(msg) => Ok(msg),
+            Err(msg) => Err(Error::new(ErrorKind::InvalidData, msg)),
+        }
+    }
+}
+
+impl Drop for NetReceiveUnix {
+    fn drop(&mut self) {
+        // the file may have been removed already
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod test_netreceiveunix {
+    use super::*;
+    use crate::Symbol;
+
+    #[test]
+    fn receive_symbol_over_unix_socket() {
+        let path =
+            std::env::temp_dir().join(format!("fudi-rs-{}-receive.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let nr = NetReceiveUnix::new(&path).expect("binding failed");
+
+        let ns = NetSendUnix::new(&path).expect("creating netsend failed");
+        ns.send(&PdMessage::Symbol(Symbol::escaped("foo")))
+            .expect("sending symbol failed");
+        let msg = nr.receive().expect("receiving failed");
+        assert_eq!(Some("foo"), msg.as_symbol());
+
+        drop(nr);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,65 @@
+//! Carry FUDI messages in WebSocket frames (e.g. for a server bridging
+//! a web app and Pure Data). The frame payload is the FUDI bytes.
+
+use crate::{codec, ParseError, PdMessage};
+use tungstenite::Message;
+
+/// Create a binary WebSocket frame containing the message.
+///
+/// # Arguments
+/// * `msg` - message to put into the frame
+pub fn binary_frame(msg: &PdMessage) -> Message {
+    Message::binary(codec::encode(msg))
+}
+
+/// Create a text WebSocket frame containing the message
+/// (e.g. for browsers handling text frames only).
+///
+/// # Arguments
+/// * `msg` - message to put into the frame
+pub fn text_frame(msg: &PdMessage) -> Message {
+    Message::text(msg.to_text())
+}
+
+/// Get the message contained in a text or binary WebSocket frame.
+/// Returns `None` for control frames (e.g. ping or close).
+///
+/// # Arguments
+/// * `frame` - received WebSocket frame
+pub fn decode_frame(frame: &Message) -> Option<Result<PdMessage, ParseError>> {
+    match frame {
+        Message::Text(text) => Some(codec::decode(text.as_bytes())),
+        Message::Binary(data) => Some(codec::decode(data)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test_ws {
+    use super::*;
+    use crate::{Atom, GenericMessage};
+
+    #[test]
+    fn frame_round_trip() {
+        let msg = PdMessage::Generic(GenericMessage::new(
+            "note",
+            vec![Atom::Int(60), Atom::Float(0.5)],
+        ));
+
+        let frame = binary_frame(&msg);
+        assert!(frame.is_binary());
+        assert_eq!(b"note 60 0.5;\n", &frame.clone().into_data()[..]);
+        assert_eq!(Some(Ok(msg.clone())), decode_frame(&frame));
+
+        let frame = text_frame(&msg);
+        assert!(frame.is_text());
+        assert_eq!(Some(Ok(msg)), decode_frame(&frame));
+
+        assert_eq!(None, decode_frame(&Message::Ping(Default::default())));
+        let frame = Message::text("bang");
+        assert_eq!(
+            Some(Err(ParseError::MissingTerminator)),
+            decode_frame(&frame)
+        );
+    }
+}